@@ -0,0 +1,10 @@
+//! Benchmarks every case of every day, see `common::bench` for the options.
+
+use common::*;
+
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+fn main() -> Result<()> {
+    init();
+    bench::main(DAYS)
+}
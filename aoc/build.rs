@@ -0,0 +1,80 @@
+//! Discovers all `dayN` crates in the workspace and generates a module for
+//! each of them, together with the `DAYS` registry that is used by `main`.
+//!
+//! Cargo cannot add dependencies from a build script, so every dependency of
+//! a day has to be listed in `aoc/Cargo.toml` as well. This is checked here,
+//! a missing one fails the build with the line to add.
+
+use std::{env, fmt::Write, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let workspace = Path::new(&manifest_dir).parent().unwrap();
+
+    let mut days: Vec<(u8, String)> = fs::read_dir(workspace)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let nr = path
+                .file_name()?
+                .to_str()?
+                .strip_prefix("day")?
+                .parse()
+                .ok()?;
+            let main = path.join("src/main.rs");
+            main.is_file()
+                .then(|| (nr, main.to_str().unwrap().to_owned()))
+        })
+        .collect();
+    days.sort();
+    check_dependencies(workspace, &days);
+
+    let mut out = String::new();
+    for (nr, main) in &days {
+        writeln!(out, "#[path = {main:?}]\npub mod day{nr};").unwrap();
+        println!(
+            "cargo::rerun-if-changed={}",
+            Path::new(main).parent().unwrap().display()
+        );
+    }
+    writeln!(out, "pub static DAYS: &[common::Day] = &[").unwrap();
+    for (nr, main) in &days {
+        let dir = Path::new(main).parent().unwrap().to_str().unwrap();
+        writeln!(
+            out,
+            "    common::Day {{ name: \"day{nr}\", dir: {dir:?}, cases: day{nr}::CASES }},"
+        )
+        .unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("days.rs"), out).unwrap();
+
+    println!("cargo::rustc-cfg=aoc_runner");
+    println!("cargo::rerun-if-changed=build.rs");
+}
+
+/// Fails the build if a day depends on a crate that `aoc` does not.
+fn check_dependencies(workspace: &Path, days: &[(u8, String)]) {
+    let dependencies = |manifest: &Path| -> Vec<String> {
+        println!("cargo::rerun-if-changed={}", manifest.display());
+        let text = fs::read_to_string(manifest).unwrap();
+        let manifest: toml::Table = text.parse().unwrap();
+        manifest
+            .get("dependencies")
+            .and_then(|deps| deps.as_table())
+            .map(|deps| deps.keys().cloned().collect())
+            .unwrap_or_default()
+    };
+    let ours = dependencies(&workspace.join("aoc/Cargo.toml"));
+    for (nr, _) in days {
+        for dep in dependencies(&workspace.join(format!("day{nr}/Cargo.toml"))) {
+            if !ours.contains(&dep) {
+                panic!(
+                    "day{nr} depends on `{dep}`, add `{dep}.workspace = true` to aoc/Cargo.toml"
+                );
+            }
+        }
+    }
+}
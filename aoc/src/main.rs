@@ -0,0 +1,191 @@
+use common::*;
+use std::{
+    env, fs,
+    io::{self, Read},
+    process,
+};
+
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+const USAGE: &str = "\
+Usage:
+    aoc list
+    aoc check [<day|all>] [--inputs <dir>] [--include-ignored]
+    aoc run <day|all> [<part>] [--input <file>] [--inputs <dir>] [--bench <runs>] [--json]
+            [--include-ignored] [--bless] [--animate] [--fps <n>]
+
+Without `--input`, all inputs of the part are read from the `src` directory
+of the day (or from `$AOC_INPUTS/<day>` when set). Use `--input -` to read the
+input from stdin.
+
+With `--bench <runs>` every part is run that many times, followed by a table
+with min, median and max wall time (summed per day when running `all`).
+
+`check` runs every case and compares the answers with the expected values
+from `boilerplate!` (or `answers.toml`), it fails if any answer is wrong.
+
+With `--bless` the answers are saved in `answers.toml` next to the inputs,
+tests without an expected value in `boilerplate!` are checked against it.
+
+With `--animate` (or `--fps <n>`) some days show an animation of their
+simulation in the terminal: space pauses, n steps, f fast-forwards and q stops.";
+
+fn main() -> Result<()> {
+    init();
+    let mut args = env::args().skip(1);
+    match args.next().as_deref() {
+        Some("list") if args.next().is_none() => list(),
+        Some("check") => {
+            let mut day = None;
+            let mut options = Options::default();
+            while let Some(arg) = args.next() {
+                if !options.parse_arg(&arg, &mut args)? {
+                    if arg.starts_with("--") || day.is_some() {
+                        bail!("unexpected argument: {arg:?}\n\n{USAGE}");
+                    }
+                    day = Some(arg);
+                }
+            }
+            check(day.as_deref().unwrap_or("all"), &options)
+        }
+        Some("run") => {
+            let mut positional = vec![];
+            let mut input = None;
+            let mut options = Options::default();
+            while let Some(arg) = args.next() {
+                if arg == "--input" {
+                    input = Some(
+                        args.next()
+                            .ok_or_else(|| eyre!("missing file after `--input`"))?,
+                    );
+                } else if !options.parse_arg(&arg, &mut args)? {
+                    if arg.starts_with("--") {
+                        bail!("unexpected argument: {arg:?}\n\n{USAGE}");
+                    }
+                    positional.push(arg);
+                }
+            }
+            match positional.as_slice() {
+                [day] => run(day, None, input.as_deref(), &options),
+                [day, part] => run(day, Some(part), input.as_deref(), &options),
+                _ => bail!("{USAGE}"),
+            }
+        }
+        _ => bail!("{USAGE}"),
+    }
+}
+
+fn list() -> Result<()> {
+    for day in DAYS {
+        let parts = day.cases.iter().map(|c| c.part).unique().join(", ");
+        println!("{}: {parts}", day.name);
+    }
+    Ok(())
+}
+
+fn check(day: &str, options: &Options) -> Result<()> {
+    let results = select_days(day)?
+        .into_iter()
+        .map(|day| Ok((day.name, check_day(day, options)?)))
+        .collect::<Result<Vec<_>>>()?;
+    let (summary, passed) = common::summary(&results);
+    print!("{summary}");
+    if !passed {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn run(day: &str, part: Option<&str>, input: Option<&str>, options: &Options) -> Result<()> {
+    let days = select_days(day)?;
+
+    let input = match input {
+        Some("-") => Some(read_stdin()?),
+        Some(path) => {
+            Some(fs::read_to_string(path).wrap_err_with(|| format!("could not read {path:?}"))?)
+        }
+        None => None,
+    };
+    if input.is_some() && days.len() > 1 {
+        bail!("a custom input can only be used for a single day");
+    }
+    if input.is_some() && options.bless {
+        bail!("answers of a custom input cannot be blessed");
+    }
+
+    let mut report = TimingReport::new(options.bench.unwrap_or(1));
+    for day in days {
+        let cases = day
+            .cases
+            .iter()
+            .filter(|c| part.is_none_or(|part| c.part == part))
+            .collect_vec();
+        if cases.is_empty() {
+            bail!("{} has no part {:?}", day.name, part.unwrap_or_default());
+        }
+        match &input {
+            // The solvers want `&'static str` inputs, it is just one input, so
+            // we can afford to leak it.
+            Some(input) => {
+                let mut inputs = Inputs::fixed(input.clone().leak());
+                let cases = solver_per_part(&cases);
+                options.run(day, &cases.iter().collect_vec(), &mut inputs, &mut report)?;
+            }
+            None => options.run(day, &cases, &mut options.inputs(day), &mut report)?,
+        }
+    }
+    options.finish(&report);
+    Ok(())
+}
+
+fn select_days(day: &str) -> Result<Vec<&'static Day>> {
+    Ok(if day == "all" {
+        DAYS.iter().collect_vec()
+    } else {
+        let nr: u8 = day
+            .strip_prefix("day")
+            .unwrap_or(day)
+            .parse()
+            .wrap_err_with(|| format!("invalid day: {day:?}"))?;
+        let day = DAYS
+            .iter()
+            .find(|d| d.nr() == Some(nr))
+            .ok_or_else(|| eyre!("day{nr} not found"))?;
+        vec![day]
+    })
+}
+
+/// Selects one case per part to run a custom input with. Parts can have
+/// parameters that depend on the input (e.g. the size of the grid), so prefer
+/// the parameters that are used for the `real` input. The attributes of the
+/// case do not apply to the custom input.
+fn solver_per_part(cases: &[&Case]) -> Vec<Case> {
+    cases
+        .iter()
+        .copied()
+        .filter(|c| !c.attrs.should_fail)
+        .into_group_map_by(|c| c.part)
+        .into_values()
+        .map(|group| {
+            let last = *group.last().unwrap();
+            group
+                .into_iter()
+                .find(|c| c.input == "real")
+                .unwrap_or(last)
+        })
+        .sorted_by_key(|c| cases.iter().position(|o| o.part == c.part))
+        .map(|c| Case {
+            attrs: Attrs::NONE,
+            expected: None,
+            ..*c
+        })
+        .collect()
+}
+
+fn read_stdin() -> Result<String> {
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .wrap_err("could not read input from stdin")?;
+    Ok(input)
+}
@@ -0,0 +1,184 @@
+//! Animations of simulations in the terminal, switched on with `--animate`.
+//!
+//! Keys: `space` pauses (and resumes), `n` or `→` shows the next frame while
+//! paused, `f` toggles fast-forward, `+` and `-` change the speed and `q`
+//! stops the animation (the solver keeps running).
+
+use crate::CLEAR_TERM;
+use std::{
+    fmt::Display,
+    io::{self, IsTerminal, Write},
+    process,
+    sync::atomic::{AtomicU32, Ordering},
+    thread::sleep,
+    time::{Duration, Instant},
+};
+use termion::{
+    AsyncReader,
+    event::Key,
+    input::{Keys, TermRead},
+    raw::{IntoRawMode, RawTerminal},
+};
+
+/// Frames per second of animations, 0 means animations are off.
+static FPS: AtomicU32 = AtomicU32::new(0);
+
+/// Frame rate while fast-forwarding, all other frames are skipped.
+const FAST_FORWARD_FPS: f64 = 30.0;
+
+pub const DEFAULT_FPS: u32 = 10;
+
+/// Switches animations on (with the given frame rate) or off (with 0), used
+/// by the `--animate` option.
+pub fn set_animation_fps(fps: u32) {
+    FPS.store(fps, Ordering::Relaxed);
+}
+
+/// Shows a stream of frames (e.g. a [`Render`](crate::Render) of a grid with
+/// overlays) in the terminal, but only if animations were switched on. Solvers
+/// can always create one, it does nothing in tests or normal runs.
+pub struct Animator {
+    enabled: bool,
+    fps: f64,
+    paused: bool,
+    fast_forward: bool,
+    last: Option<Instant>,
+    frames: usize,
+    // Only when stdin and stdout are terminals.
+    keys: Option<(Keys<AsyncReader>, RawTerminal<io::Stdout>)>,
+}
+
+impl Default for Animator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Animator {
+    pub fn new() -> Self {
+        let fps = FPS.load(Ordering::Relaxed);
+        let enabled = fps > 0;
+        let keys = (enabled && io::stdin().is_terminal())
+            .then(|| {
+                Some((
+                    termion::async_stdin().keys(),
+                    io::stdout().into_raw_mode().ok()?,
+                ))
+            })
+            .flatten();
+        Self {
+            enabled,
+            fps: fps as f64,
+            paused: false,
+            fast_forward: false,
+            last: None,
+            frames: 0,
+            keys,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Shows the next frame, waits as long as needed to limit the frame rate
+    /// (or while paused).
+    pub fn frame(&mut self, frame: impl Display) {
+        self.frame_with_status(frame, "");
+    }
+
+    /// Shows the next frame with a status line below it.
+    pub fn frame_with_status(&mut self, frame: impl Display, status: impl Display) {
+        if !self.enabled {
+            return;
+        }
+        self.frames += 1;
+        let fps = if self.fast_forward {
+            FAST_FORWARD_FPS.max(self.fps)
+        } else {
+            self.fps
+        };
+        let interval = Duration::from_secs_f64(1.0 / fps);
+        if self.fast_forward && self.last.is_some_and(|last| last.elapsed() < interval) {
+            // Skip this frame, but still handle the keyboard.
+            self.handle_keys();
+            return;
+        }
+        if let Some(last) = self.last {
+            sleep(interval.saturating_sub(last.elapsed()));
+        }
+
+        let text = format!(
+            "{CLEAR_TERM}{frame}\n{status}\nframe {}{}\n",
+            self.frames,
+            self.state()
+        );
+        // Raw mode needs explicit carriage returns.
+        let text = if self.keys.is_some() {
+            text.replace('\n', "\r\n")
+        } else {
+            text
+        };
+        let mut out = io::stdout().lock();
+        // Ignore errors (e.g. a closed pipe), this is only for debugging.
+        let _ = out.write_all(text.as_bytes()).and_then(|_| out.flush());
+        drop(out);
+
+        self.last = Some(Instant::now());
+        self.handle_keys();
+        while self.paused && self.enabled {
+            sleep(Duration::from_millis(20));
+            if self.handle_keys() {
+                break;
+            }
+        }
+    }
+
+    fn state(&self) -> String {
+        if self.keys.is_none() {
+            String::new()
+        } else if self.paused {
+            " [paused] space: resume, n: next frame, q: stop".into()
+        } else if self.fast_forward {
+            " [fast-forward] f: normal speed, space: pause, q: stop".into()
+        } else {
+            format!(
+                " [{} fps] space: pause, f: fast-forward, +/-: speed, q: stop",
+                self.fps
+            )
+        }
+    }
+
+    /// Handles pending key presses, returns whether the next frame should be
+    /// shown (when paused).
+    fn handle_keys(&mut self) -> bool {
+        let Some((keys, _)) = &mut self.keys else {
+            return false;
+        };
+        let mut step = false;
+        let mut stop = false;
+        while let Some(Ok(key)) = keys.next() {
+            match key {
+                Key::Char(' ') => self.paused = !self.paused,
+                Key::Char('n') | Key::Right => step = true,
+                Key::Char('f') => self.fast_forward = !self.fast_forward,
+                Key::Char('+') => self.fps *= 2.0,
+                Key::Char('-') => self.fps = (self.fps / 2.0).max(0.5),
+                Key::Char('q') | Key::Esc => stop = true,
+                Key::Ctrl('c') => {
+                    // Raw mode swallows the interrupt, restore the terminal
+                    // before exiting.
+                    self.keys = None;
+                    process::exit(130);
+                }
+                _ => (),
+            }
+        }
+        if stop {
+            self.enabled = false;
+            // Restores the terminal.
+            self.keys = None;
+        }
+        step || stop
+    }
+}
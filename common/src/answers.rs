@@ -0,0 +1,77 @@
+use crate::{Case, Result, WrapErr};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use toml::{Table, Value};
+
+/// Name of the file with known answers, it lives next to the inputs (so
+/// private answers can be kept together with the private inputs).
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Known answers of a day, keyed by part and input (including the params),
+/// e.g.:
+///
+/// ```toml
+/// [part1]
+/// test = 12
+/// "real(101, 103)" = 220971520
+/// ```
+#[derive(Debug)]
+pub struct Answers {
+    path: PathBuf,
+    table: Table,
+}
+
+impl Answers {
+    /// Loads the answers from `dir`, a missing file means there are no known
+    /// answers yet.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(ANSWERS_FILE);
+        let table = match fs::read_to_string(&path) {
+            Ok(text) => text.parse().wrap_err_with(|| format!("invalid {path:?}"))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Table::new(),
+            Err(e) => return Err(e).wrap_err_with(|| format!("could not read {path:?}")),
+        };
+        Ok(Self { path, table })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self, case: &Case) -> Option<String> {
+        match self.table.get(case.part)?.get(key(case))? {
+            Value::String(s) => Some(s.clone()),
+            other => Some(other.to_string()),
+        }
+    }
+
+    /// Records an answer, numbers are stored as TOML integers.
+    pub fn set(&mut self, case: &Case, answer: &str) {
+        let value = answer
+            .parse()
+            .map_or_else(|_| answer.into(), Value::Integer);
+        let part = self
+            .table
+            .entry(case.part)
+            .or_insert_with(|| Table::new().into());
+        if !part.is_table() {
+            *part = Table::new().into();
+        }
+        part.as_table_mut().unwrap().insert(key(case), value);
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(&self.path, self.table.to_string())
+            .wrap_err_with(|| format!("could not write {:?}", self.path))
+    }
+}
+
+fn key(case: &Case) -> String {
+    if case.params.is_empty() {
+        case.input.into()
+    } else {
+        format!("{}({})", case.input, case.params)
+    }
+}
@@ -0,0 +1,320 @@
+//! A small benchmark harness in the spirit of Criterion that runs on stable
+//! (with `harness = false`), used by `cargo bench -p aoc` and by the `bench`
+//! command of every day (`cargo run --release -p day20 -- bench`).
+//!
+//! Every case is warmed up first, then a number of samples is taken (each
+//! sample runs the case a number of iterations), outliers are rejected with
+//! Tukey's fences and the result is compared with a saved baseline.
+
+use crate::{Case, Day, Inputs, Result, WrapErr, bail, eyre};
+use serde_json::{Map, Value};
+use std::{
+    env, fs,
+    hint::black_box,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+/// Changes smaller than this are considered noise.
+const NOISE_THRESHOLD: f64 = 0.02;
+
+const USAGE: &str = "\
+Usage: cargo bench -p aoc -- [<filter>...] [options]
+       cargo run --release -p <day> -- bench [<filter>...] [options]
+
+Options:
+    --warm-up <secs>          warm-up time per case (default: 0.5)
+    --measurement <secs>      measurement time per case (default: 2)
+    --samples <n>             number of samples per case (default: 50)
+    --baseline <name>         compare with this baseline (default: base)
+    --save-baseline <name>    save results as this baseline
+    --inputs <dir>            read inputs from this directory (for every day)
+
+Cases are named like `day20, go, real(2, 100)`. A filter matches a whole name
+or the start of one up to a comma, e.g. `day20` or `day20, go`.";
+
+pub struct Config {
+    pub warm_up: Duration,
+    pub measurement: Duration,
+    pub samples: usize,
+    pub baseline: String,
+    pub save_baseline: Option<String>,
+    pub inputs: Option<PathBuf>,
+    pub filters: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            warm_up: Duration::from_millis(500),
+            measurement: Duration::from_secs(2),
+            samples: 50,
+            baseline: "base".into(),
+            save_baseline: None,
+            inputs: None,
+            filters: vec![],
+        }
+    }
+}
+
+impl Config {
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut config = Self::default();
+        let mut args = args.into_iter();
+        let secs = |v: String| -> Result<Duration> {
+            Duration::try_from_secs_f64(v.parse()?).wrap_err_with(|| format!("invalid time: {v}"))
+        };
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| eyre!("missing value after `{arg}`"))
+            };
+            match arg.as_str() {
+                // passed by `cargo bench`
+                "--bench" => (),
+                "--warm-up" => config.warm_up = secs(value()?)?,
+                "--measurement" => config.measurement = secs(value()?)?,
+                "--samples" => config.samples = value()?.parse::<usize>()?.max(2),
+                "--baseline" => config.baseline = value()?,
+                "--save-baseline" => config.save_baseline = Some(value()?),
+                "--inputs" => config.inputs = Some(value()?.into()),
+                "--help" | "-h" => bail!("{USAGE}"),
+                _ if arg.starts_with("--") => bail!("unexpected argument: {arg:?}\n\n{USAGE}"),
+                _ => config.filters.push(arg),
+            }
+        }
+        Ok(config)
+    }
+
+    /// Whether a case name matches one of the filters, see [`USAGE`].
+    fn matches(&self, name: &str) -> bool {
+        self.filters.is_empty()
+            || self.filters.iter().any(|filter| {
+                name.strip_prefix(filter.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with(','))
+            })
+    }
+}
+
+/// Statistics of a single case, all times are per iteration.
+#[derive(Debug, Clone, Copy)]
+pub struct Estimate {
+    pub mean: Duration,
+    pub median: Duration,
+    pub std_dev: Duration,
+    pub outliers: usize,
+}
+
+impl Estimate {
+    /// Rejects outliers (outside Tukey's fences) and calculates the statistics
+    /// of the remaining samples.
+    pub fn from_samples(mut samples: Vec<f64>) -> Self {
+        samples.sort_by(f64::total_cmp);
+        let q1 = quantile(&samples, 0.25);
+        let q3 = quantile(&samples, 0.75);
+        let fence = 1.5 * (q3 - q1);
+        let total = samples.len();
+        samples.retain(|&s| (q1 - fence..=q3 + fence).contains(&s));
+
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0).max(1.0);
+        Self {
+            mean: Duration::from_secs_f64(mean),
+            median: Duration::from_secs_f64(quantile(&samples, 0.5)),
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+            outliers: total - samples.len(),
+        }
+    }
+}
+
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
+
+/// Benchmarks a single function according to the config.
+pub fn bench(config: &Config, mut f: impl FnMut()) -> Estimate {
+    // Warm up and get a rough estimate of a single iteration in the meantime.
+    let start = Instant::now();
+    let mut iterations = 0_u32;
+    while iterations == 0 || start.elapsed() < config.warm_up {
+        f();
+        iterations += 1;
+    }
+    let per_iteration = start.elapsed() / iterations;
+
+    // Slow cases get fewer iterations per sample (and possibly take longer
+    // than the measurement time), fast ones get more.
+    let per_sample = config.measurement / config.samples as u32;
+    let iterations = (per_sample.as_secs_f64() / per_iteration.as_secs_f64().max(1e-9))
+        .clamp(1.0, u32::MAX as f64) as u32;
+    let samples = (0..config.samples)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..iterations {
+                f();
+            }
+            start.elapsed().as_secs_f64() / iterations as f64
+        })
+        .collect();
+    Estimate::from_samples(samples)
+}
+
+/// Runs all cases of the given days that match the filters of the command
+/// line.
+pub fn main(days: &[Day]) -> Result<()> {
+    run(days, &Config::from_args(env::args().skip(1))?)
+}
+
+/// Runs all cases of the given days that match the filters, compares them with
+/// the baseline and saves them if `--save-baseline` was given. A baseline is
+/// never updated otherwise, so a regression keeps being reported until it is
+/// fixed or a new baseline is saved on purpose.
+pub fn run(days: &[Day], config: &Config) -> Result<()> {
+    let baselines = baseline_dir()?;
+    let load = |name: &str| -> Result<Map<String, Value>> {
+        let path = baselines.join(format!("{name}.json"));
+        match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).wrap_err_with(|| format!("invalid {path:?}")),
+            Err(_) => Ok(Map::new()),
+        }
+    };
+    let baseline = load(&config.baseline)?;
+    // Cases that are not run keep their saved results.
+    let mut saved = match &config.save_baseline {
+        Some(name) => Some(load(name)?),
+        None => None,
+    };
+
+    for day in days {
+        let mut inputs = Inputs::new(day.input_dir(config.inputs.as_deref()));
+        let cases: Vec<(String, &Case)> = day
+            .cases
+            .iter()
+            .map(|case| (format!("{}, {}", day.name, case.label()), case))
+            .filter(|(name, _)| config.matches(name))
+            .collect();
+        for (name, case) in cases {
+            if case.attrs.ignore || case.attrs.should_fail {
+                continue;
+            }
+            let Some(input) = inputs.get_if_present(case)? else {
+                continue;
+            };
+            // Make sure that the case works at all, before we start measuring.
+            (case.solve)(input).wrap_err_with(|| format!("{name} failed"))?;
+            let estimate = bench(config, || {
+                let _ = black_box((case.solve)(black_box(input)));
+            });
+            report(
+                &name,
+                &estimate,
+                baseline.get(&name).and_then(Value::as_f64),
+            );
+            if let Some(saved) = &mut saved {
+                saved.insert(name, estimate.mean.as_secs_f64().into());
+            }
+        }
+    }
+
+    let (Some(name), Some(saved)) = (&config.save_baseline, saved) else {
+        return Ok(());
+    };
+    fs::create_dir_all(&baselines)?;
+    let path = baselines.join(format!("{name}.json"));
+    fs::write(&path, serde_json::to_string_pretty(&saved)?)
+        .wrap_err_with(|| format!("could not save baseline to {path:?}"))
+}
+
+fn report(name: &str, estimate: &Estimate, baseline: Option<f64>) {
+    let Estimate {
+        mean,
+        median,
+        std_dev,
+        outliers,
+    } = estimate;
+    println!("{name}");
+    println!("    time:     {mean:.2?} ± {std_dev:.2?} (median {median:.2?})");
+    if *outliers > 0 {
+        println!("    outliers: {outliers} rejected");
+    }
+    if let Some(old) = baseline {
+        let new = mean.as_secs_f64();
+        let change = (new - old) / old;
+        // Only report a difference if it is larger than both the noise
+        // threshold and the spread of the measurements.
+        let verdict =
+            if change.abs() < NOISE_THRESHOLD || (new - old).abs() < 2.0 * std_dev.as_secs_f64() {
+                "no change"
+            } else if change > 0.0 {
+                "REGRESSED"
+            } else {
+                "improved"
+            };
+        println!("    change:   {:+.2}% ({verdict})", change * 100.0);
+    }
+}
+
+/// Baselines are stored in `<target>/aoc-bench`. The benchmark executable
+/// itself lives in `<target>/<profile>/deps`, the day binaries in
+/// `<target>/<profile>`.
+fn baseline_dir() -> Result<PathBuf> {
+    if let Some(target) = env::var_os("CARGO_TARGET_DIR") {
+        return Ok(PathBuf::from(target).join("aoc-bench"));
+    }
+    let exe = env::current_exe()?;
+    let mut dir = exe.parent();
+    if dir.is_some_and(|dir| dir.ends_with("deps")) {
+        dir = dir.and_then(Path::parent);
+    }
+    let target = dir
+        .and_then(Path::parent)
+        .ok_or_else(|| eyre!("could not determine target directory from {exe:?}"))?;
+    Ok(target.join("aoc-bench"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_secs(duration: Duration, secs: f64) {
+        assert!(
+            (duration.as_secs_f64() - secs).abs() < 1e-6,
+            "{duration:?} != {secs}s"
+        );
+    }
+
+    #[test]
+    fn estimate_rejects_outliers() {
+        let estimate = Estimate::from_samples(vec![4.0, 100.0, 2.0, 1.0, 3.0]);
+        assert_eq!(estimate.outliers, 1);
+        assert_secs(estimate.mean, 2.5);
+        assert_secs(estimate.median, 2.5);
+        assert_secs(estimate.std_dev, (5.0_f64 / 3.0).sqrt());
+
+        let estimate = Estimate::from_samples(vec![0.5]);
+        assert_eq!(estimate.outliers, 0);
+        assert_secs(estimate.mean, 0.5);
+        assert_secs(estimate.std_dev, 0.0);
+    }
+
+    #[test]
+    fn filters_match_whole_parts_of_names() {
+        let args = ["--bench", "day2", "day20, go", "--save-baseline", "new"];
+        let config = Config::from_args(args.map(String::from)).unwrap();
+        assert_eq!(
+            (config.baseline.as_str(), config.save_baseline.as_deref()),
+            ("base", Some("new"))
+        );
+        assert!(config.matches("day2, part1, test"));
+        assert!(config.matches("day20, go, real(2, 100)"));
+        assert!(!config.matches("day21, part1, test"));
+        assert!(!config.matches("day20, gone"));
+        assert!(Config::default().matches("day21, part1, test"));
+
+        assert!(Config::from_args(["--baseline".to_string()]).is_err());
+        assert!(Config::from_args(["--unknown".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{Answers, Case, Day, Itertools, Options, Result};
+use std::{
+    env,
+    fmt::Write,
+    io::{self, IsTerminal},
+    time::{Duration, Instant},
+};
+
+/// The outcome of checking a single case against its expected answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail(String),
+    /// The case ran, but there is no expected answer to compare with.
+    Unknown(String),
+    Skipped(&'static str),
+}
+
+#[derive(Debug, Clone)]
+pub struct Checked {
+    pub case: Case,
+    pub status: Status,
+    pub time: Duration,
+}
+
+/// Runs every case of the day and compares the answers with the expected
+/// values from `boilerplate!` or `answers.toml`. Panics and errors of solvers
+/// are reported as failures.
+pub fn check_day(day: &Day, options: &Options) -> Result<Vec<Checked>> {
+    let mut inputs = options.inputs(day);
+    let answers = Answers::load(&day.input_dir(options.inputs.as_deref()))?;
+    Ok(day
+        .cases
+        .iter()
+        .map(|case| {
+            let checked = |status, time| Checked {
+                case: *case,
+                status,
+                time,
+            };
+            if case.attrs.ignore && !options.include_ignored {
+                return checked(Status::Skipped("ignored"), Duration::ZERO);
+            }
+            let input = match inputs.get_if_present(case) {
+                Ok(Some(input)) => input,
+                Ok(None) => return checked(Status::Skipped("input not found"), Duration::ZERO),
+                Err(error) => return checked(Status::Fail(format!("{error}")), Duration::ZERO),
+            };
+            let start = Instant::now();
+            let result = case.solve_catching(input);
+            let time = start.elapsed();
+            let expected = case.expected.map(|f| f()).or_else(|| answers.get(case));
+            let status = match (result, expected) {
+                (Err(_), _) if case.attrs.should_fail => Status::Pass,
+                (Ok(answer), _) if case.attrs.should_fail => {
+                    Status::Fail(format!("should fail, but returned {answer}"))
+                }
+                (Err(error), _) => Status::Fail(format!("{error:#}")),
+                (Ok(answer), Some(expected)) if answer == expected => Status::Pass,
+                (Ok(answer), Some(expected)) => {
+                    Status::Fail(format!("expected {expected}, got {answer}"))
+                }
+                (Ok(answer), None) => Status::Unknown(answer),
+            };
+            checked(status, time)
+        })
+        .collect())
+}
+
+/// A table with one row per day (failures and unknown answers are listed
+/// below the day), followed by a summary line. Returns the table and whether
+/// all cases passed.
+pub fn summary(results: &[(&'static str, Vec<Checked>)]) -> (String, bool) {
+    let color = io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+    let paint = |text: &str, code: &str| {
+        if color {
+            format!("\x1b[{code}m{text}\x1b[0m")
+        } else {
+            text.into()
+        }
+    };
+    const GREEN: &str = "1;32";
+    const RED: &str = "1;31";
+    const YELLOW: &str = "1;33";
+    const DIM: &str = "2";
+
+    let mut out = String::new();
+    let all = results.iter().flat_map(|(_, cs)| cs).collect_vec();
+    let count = |f: fn(&Status) -> bool| all.iter().filter(|c| f(&c.status)).count();
+    for (day, checked) in results {
+        let time: Duration = checked.iter().map(|c| c.time).sum();
+        let ran = checked
+            .iter()
+            .filter(|c| !matches!(c.status, Status::Skipped(_)))
+            .count();
+        let passed = checked.iter().filter(|c| c.status == Status::Pass).count();
+        let (label, code) = if checked.iter().any(|c| matches!(c.status, Status::Fail(_))) {
+            ("FAIL", RED)
+        } else if ran == 0 {
+            ("SKIP", YELLOW)
+        } else if passed < ran {
+            ("UNKNOWN", YELLOW)
+        } else {
+            ("PASS", GREEN)
+        };
+        let skipped = match checked.len() - ran {
+            0 => String::new(),
+            n => paint(&format!(" ({n} skipped)"), DIM),
+        };
+        writeln!(
+            out,
+            "{} [{:>10}] {day:<6} {passed}/{ran} passed{skipped}",
+            paint(&format!("{label:>12}"), code),
+            format!("{time:.2?}"),
+        )
+        .unwrap();
+        for c in checked {
+            let (what, code) = match &c.status {
+                Status::Fail(reason) => (reason.clone(), RED),
+                Status::Unknown(answer) => (format!("{answer} (no expected answer)"), YELLOW),
+                _ => continue,
+            };
+            writeln!(out, "{:>25} {}: {}", "", c.case.label(), paint(&what, code)).unwrap();
+        }
+    }
+
+    let failed = count(|s| matches!(s, Status::Fail(_)));
+    let time: Duration = all.iter().map(|c| c.time).sum();
+    writeln!(
+        out,
+        "{} [{:>10}] {} days, {} cases: {} passed, {} failed, {} unknown, {} skipped",
+        paint(
+            &format!("{:>12}", "Summary"),
+            if failed > 0 { RED } else { GREEN }
+        ),
+        format!("{time:.2?}"),
+        results.len(),
+        all.len(),
+        paint(&count(|s| *s == Status::Pass).to_string(), GREEN),
+        paint(&failed.to_string(), if failed > 0 { RED } else { DIM }),
+        count(|s| matches!(s, Status::Unknown(_))),
+        count(|s| matches!(s, Status::Skipped(_))),
+    )
+    .unwrap();
+    (out, failed == 0)
+}
@@ -0,0 +1,148 @@
+use crate::{DIRECTIONS_4, Grid, Itertools, Neighbourhood, Pos2};
+use std::collections::VecDeque;
+
+/// The connected components (regions) of a grid, see [`Grid::components`].
+#[derive(Clone)]
+pub struct Components {
+    /// The label of every cell, an index in `stats`.
+    pub labels: Grid<usize>,
+    pub stats: Vec<Component>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Component {
+    /// The first cell of the component (in reading order).
+    pub start: Pos2,
+    /// The number of cells.
+    pub area: usize,
+    /// The number of cell edges that border another component or the edge of
+    /// the grid.
+    pub perimeter: usize,
+    /// The smallest and largest position (inclusive) of the cells.
+    pub min: Pos2,
+    pub max: Pos2,
+    /// The number of corners of the outline (including the outlines of holes),
+    /// which is also the number of straight sides.
+    pub corners: usize,
+}
+
+impl Component {
+    /// The number of straight sides, the same as the number of corners.
+    pub fn sides(&self) -> usize {
+        self.corners
+    }
+}
+
+impl<T: Copy + PartialEq> Grid<T> {
+    /// Labels the connected components: neighbouring cells (in
+    /// `neighbourhood`) are in the same component when `same` holds for them.
+    /// Perimeter and corners are always about the 4 sides of the cells.
+    pub fn components(
+        &self,
+        neighbourhood: Neighbourhood,
+        same: impl Fn(T, T) -> bool,
+    ) -> Components {
+        const UNLABELED: usize = usize::MAX;
+        let mut labels = Grid::new(self.rows(), self.columns(), UNLABELED);
+        let mut starts = vec![];
+        let mut queue = VecDeque::new();
+        for start in self.keys() {
+            if labels[start] != UNLABELED {
+                continue;
+            }
+            let label = starts.len();
+            starts.push(start);
+            labels[start] = label;
+            queue.push_back(start);
+            while let Some(pos) = queue.pop_front() {
+                for (next, cell) in self.neighbours(pos, neighbourhood) {
+                    if labels[next] == UNLABELED && same(self[pos], cell) {
+                        labels[next] = label;
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        let mut stats = starts
+            .iter()
+            .map(|&start| Component {
+                start,
+                area: 0,
+                perimeter: 0,
+                min: start,
+                max: start,
+                corners: 0,
+            })
+            .collect_vec();
+        for (pos, label) in labels.items() {
+            let in_component = |pos| labels.get(pos) == Some(label);
+            let stats = &mut stats[label];
+            stats.area += 1;
+            stats.min = Pos2(stats.min.0.min(pos.0), stats.min.1.min(pos.1));
+            stats.max = Pos2(stats.max.0.max(pos.0), stats.max.1.max(pos.1));
+            stats.perimeter += DIRECTIONS_4
+                .iter()
+                .filter(|&&dir| !in_component(pos + dir))
+                .count();
+            // Every pair of orthogonal sides of a cell is a corner of the
+            // outline if neither side is in the component (an outer corner),
+            // or if both are but the diagonal between them is not (an inner
+            // corner).
+            stats.corners += DIRECTIONS_4
+                .iter()
+                .circular_tuple_windows()
+                .filter(
+                    |&(&a, &b)| match (in_component(pos + a), in_component(pos + b)) {
+                        (false, false) => true,
+                        (true, true) => !in_component(pos + a + b),
+                        _ => false,
+                    },
+                )
+                .count();
+        }
+        Components { labels, stats }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn regions(input: &str) -> Vec<(char, usize, usize, usize)> {
+        let grid = Grid::from(input);
+        let components = grid.components(Neighbourhood::Manhattan, |a, b| a == b);
+        (components.stats.iter())
+            .map(|c| (grid[c.start], c.area, c.perimeter, c.sides()))
+            .collect()
+    }
+
+    #[test]
+    fn counts_outer_and_inner_corners() {
+        let e_shape = "EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE";
+        assert_eq!(
+            regions(e_shape),
+            [('E', 17, 36, 12), ('X', 4, 10, 4), ('X', 4, 10, 4)]
+        );
+
+        // the outlines of the holes count as well, the holes touch diagonally
+        let holes = "AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA";
+        assert_eq!(
+            regions(holes),
+            [('A', 28, 40, 12), ('B', 4, 8, 4), ('B', 4, 8, 4)]
+        );
+    }
+
+    #[test]
+    fn keeps_bounds_of_diagonal_components() {
+        let grid = Grid::from("#.\n.#");
+        let components = grid.components(Neighbourhood::All, |a, b| a == b);
+        let walls = components.stats[components.labels[Pos2(0, 0)]];
+        assert_eq!(
+            (walls.area, walls.min, walls.max),
+            (2, Pos2(0, 0), Pos2(1, 1))
+        );
+        // the corners where the cells touch count on both sides of the diagonal
+        assert_eq!((walls.perimeter, walls.corners), (8, 8));
+    }
+}
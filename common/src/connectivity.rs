@@ -0,0 +1,181 @@
+use crate::{Grid, Neighbourhood, Pos2};
+use std::collections::HashSet;
+
+/// Disjoint sets of `0..n` that can only be merged (union-find with path
+/// halving and union by size).
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+        }
+    }
+
+    /// The representative of the set of `x`.
+    pub fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Merges the sets of `a` and `b`, returns `false` if they already were
+    /// the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            (a, b) = (b, a);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The number of elements in the set of `x`.
+    pub fn size_of(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+}
+
+impl Grid<bool> {
+    /// Places `walls` on the open cells one after the other and returns the
+    /// first one after which `a` and `b` are no longer connected (with its
+    /// index in `walls`), `None` if they stay connected (or never were). Walls
+    /// outside the grid are ignored.
+    ///
+    /// Instead of searching for a path after every wall, this places all
+    /// walls and removes them again in reverse, joining the cells with a
+    /// union-find until `a` and `b` are connected again.
+    pub fn first_cut(
+        &self,
+        walls: impl IntoIterator<Item = Pos2>,
+        a: Pos2,
+        b: Pos2,
+        neighbourhood: Neighbourhood,
+    ) -> Option<(usize, Pos2)> {
+        let mut open = self.clone();
+        // Only the first wall on a cell closes it.
+        let mut seen = HashSet::new();
+        let walls: Vec<_> = walls
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, pos)| self.get(pos) == Some(true) && seen.insert(pos))
+            .collect();
+        for &(_, pos) in &walls {
+            open[pos] = false;
+        }
+
+        let columns = self.columns();
+        let index = |pos: Pos2| pos.0 * columns + pos.1;
+        let mut sets = UnionFind::new(self.rows() * columns);
+        let join = |open: &Grid<bool>, sets: &mut UnionFind, pos| {
+            for (next, is_open) in open.neighbours(pos, neighbourhood) {
+                if is_open {
+                    sets.union(index(pos), index(next));
+                }
+            }
+        };
+        for (pos, is_open) in open.items() {
+            if is_open {
+                join(&open, &mut sets, pos);
+            }
+        }
+        let connected = |open: &Grid<bool>, sets: &mut UnionFind| {
+            open[a] && open[b] && sets.connected(index(a), index(b))
+        };
+        if connected(&open, &mut sets) {
+            return None;
+        }
+        for &(i, pos) in walls.iter().rev() {
+            open[pos] = true;
+            join(&open, &mut sets, pos);
+            if connected(&open, &mut sets) {
+                return Some((i, pos));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_find_merges_sets() {
+        let mut sets = UnionFind::new(5);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(3, 4));
+        assert!(!sets.union(1, 0));
+        assert!(sets.connected(1, 0) && !sets.connected(1, 3));
+        assert!(sets.union(4, 1));
+        assert!(sets.connected(0, 3));
+        assert_eq!((sets.size_of(3), sets.size_of(2)), (4, 1));
+    }
+
+    #[test]
+    fn first_cut_finds_the_wall_that_disconnects() {
+        // a ring around a wall
+        let grid: Grid<bool> = "...\n.#.\n...".parse().unwrap();
+        let (a, b) = (Pos2(0, 0), Pos2(2, 2));
+        let cut = |walls: &[Pos2], neighbourhood| {
+            grid.first_cut(walls.iter().copied(), a, b, neighbourhood)
+        };
+        let manhattan = |walls: &[Pos2]| cut(walls, Neighbourhood::Manhattan);
+
+        assert_eq!(manhattan(&[Pos2(0, 1), Pos2(1, 2)]), None);
+        assert_eq!(manhattan(&[Pos2(0, 1), Pos2(2, 1)]), Some((1, Pos2(2, 1))));
+        // walls on cells that are already closed do not count
+        assert_eq!(
+            manhattan(&[Pos2(1, 1), Pos2(0, 1), Pos2(1, 0)]),
+            Some((2, Pos2(1, 0)))
+        );
+        assert_eq!(manhattan(&[Pos2(0, 1), Pos2(0, 1), Pos2(1, 2)]), None);
+        assert_eq!(
+            manhattan(&[Pos2(1, 0), Pos2(1, 0), Pos2(0, 1)]),
+            Some((2, Pos2(0, 1)))
+        );
+        // a wall on `a` or `b` cuts them off immediately
+        assert_eq!(
+            manhattan(&[Pos2(0, 2), Pos2(2, 2), Pos2(0, 0)]),
+            Some((1, Pos2(2, 2)))
+        );
+        assert_eq!(manhattan(&[Pos2(0, 0)]), Some((0, Pos2(0, 0))));
+
+        let walls = [Pos2(1, 0), Pos2(0, 2)];
+        assert_eq!(manhattan(&walls), Some((1, Pos2(0, 2))));
+        assert_eq!(cut(&walls, Neighbourhood::All), None);
+    }
+
+    #[test]
+    fn first_cut_of_disconnected_cells() {
+        let grid: Grid<bool> = ".#.".parse().unwrap();
+        assert_eq!(
+            grid.first_cut([], Pos2(0, 0), Pos2(0, 2), Neighbourhood::Manhattan),
+            None
+        );
+    }
+
+    #[test]
+    fn first_cut_ignores_walls_outside_the_grid() {
+        let grid: Grid<bool> = "..\n..".parse().unwrap();
+        let walls = [Pos2(0, 5), Pos2(0, 1), Pos2(9, 9), Pos2(1, 0)];
+        let cut = grid.first_cut(walls, Pos2(0, 0), Pos2(1, 1), Neighbourhood::Manhattan);
+        assert_eq!(cut, Some((3, Pos2(1, 0))));
+    }
+}
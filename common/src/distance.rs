@@ -0,0 +1,112 @@
+use crate::{Grid, Neighbourhood, Pos2};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
+
+impl<T: Copy + PartialEq> Grid<T> {
+    /// The number of steps from the nearest of `sources` to every cell (BFS),
+    /// moving only onto cells for which `passable` holds. Unreachable cells
+    /// are `None`.
+    pub fn distance_map(
+        &self,
+        sources: impl IntoIterator<Item = Pos2>,
+        neighbourhood: Neighbourhood,
+        passable: impl Fn(Pos2, T) -> bool,
+    ) -> Grid<Option<usize>> {
+        let mut distances = Grid::new(self.rows(), self.columns(), None);
+        let mut queue = VecDeque::new();
+        for source in sources {
+            if distances[source].is_none() {
+                distances[source] = Some(0);
+                queue.push_back((source, 0));
+            }
+        }
+        while let Some((pos, distance)) = queue.pop_front() {
+            for (next, cell) in self.neighbours(pos, neighbourhood) {
+                if distances[next].is_none() && passable(next, cell) {
+                    distances[next] = Some(distance + 1);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+        distances
+    }
+
+    /// Like [`distance_map`](Self::distance_map), but with the cost of every
+    /// move like [`Grid::search`] (Dijkstra): `cost(from, to)` is `None` if
+    /// the move is not possible.
+    pub fn cost_map(
+        &self,
+        sources: impl IntoIterator<Item = Pos2>,
+        neighbourhood: Neighbourhood,
+        cost: impl Fn((Pos2, T), (Pos2, T)) -> Option<usize>,
+    ) -> Grid<Option<usize>> {
+        let mut costs = Grid::new(self.rows(), self.columns(), None);
+        let mut heap = BinaryHeap::new();
+        for source in sources {
+            costs[source] = Some(0);
+            heap.push(Reverse((0, source)));
+        }
+        while let Some(Reverse((total, pos))) = heap.pop() {
+            if costs[pos].is_some_and(|best| best < total) {
+                continue;
+            }
+            for (next, cell) in self.neighbours(pos, neighbourhood) {
+                let Some(step) = cost((pos, self[pos]), (next, cell)) else {
+                    continue;
+                };
+                let total = total + step;
+                if costs[next].is_none_or(|best| total < best) {
+                    costs[next] = Some(total);
+                    heap.push(Reverse((total, next)));
+                }
+            }
+        }
+        costs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(grid: &Grid<Option<usize>>) -> Vec<Vec<Option<usize>>> {
+        grid.row_iter().map(<[_]>::to_vec).collect()
+    }
+
+    #[test]
+    fn distances_from_several_sources() {
+        let grid: Grid<bool> = "...#.\n.#...".parse().unwrap();
+        let distances = grid.distance_map(
+            [Pos2(0, 0), Pos2(0, 4)],
+            Neighbourhood::Manhattan,
+            |_, open| open,
+        );
+        assert_eq!(
+            rows(&distances),
+            [
+                [Some(0), Some(1), Some(2), None, Some(0)],
+                [Some(1), None, Some(3), Some(2), Some(1)],
+            ]
+        );
+    }
+
+    #[test]
+    fn costs_of_weighted_moves() {
+        // moving onto a digit costs the digit, `#` cannot be entered
+        let grid = Grid::from("19#\n111");
+        let cost = |_, (_, to): (Pos2, char)| to.to_digit(10).map(|d| d as usize);
+        let costs = grid.cost_map([Pos2(0, 0)], Neighbourhood::Manhattan, cost);
+        assert_eq!(
+            rows(&costs),
+            [[Some(0), Some(9), None], [Some(1), Some(2), Some(3)]]
+        );
+
+        let costs = grid.cost_map([Pos2(0, 0)], Neighbourhood::All, cost);
+        assert_eq!(
+            rows(&costs),
+            [[Some(0), Some(9), None], [Some(1), Some(1), Some(2)]]
+        );
+    }
+}
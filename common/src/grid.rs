@@ -1,13 +1,19 @@
-use crate::{Dir2, Pos2};
-use pathfinding::{matrix::Matrix, prelude::astar};
+use crate::{
+    DIRECTIONS_4, DIRECTIONS_8, Dir2, Heading, Itertools, Pos2, Result, bail, ensure, eyre,
+};
+use color_eyre::Report;
+use pathfinding::{matrix::Matrix, prelude::bfs_reach};
 use std::{
+    collections::HashMap,
     fmt::{Debug, Write},
+    hash::Hash,
     iter,
     ops::{Index, IndexMut},
+    str::FromStr,
 };
 
 #[derive(PartialEq, Eq, Hash)]
-pub struct Grid<T = char>(Matrix<T>);
+pub struct Grid<T = char>(pub(crate) Matrix<T>);
 
 impl<T: Copy> Clone for Grid<T> {
     fn clone(&self) -> Self {
@@ -31,6 +37,162 @@ pub enum Neighbourhood {
     All,
 }
 
+impl Neighbourhood {
+    pub fn directions(self) -> &'static [Dir2] {
+        match self {
+            Neighbourhood::Manhattan => &DIRECTIONS_4,
+            Neighbourhood::All => &DIRECTIONS_8,
+        }
+    }
+
+    /// The same directions as [`Heading`]s, clockwise from north.
+    pub fn headings(self) -> &'static [Heading] {
+        match self {
+            Neighbourhood::Manhattan => &Heading::CARDINAL,
+            Neighbourhood::All => &Heading::ALL,
+        }
+    }
+}
+
+/// A cell of a [`Grid`] that can be parsed from (and printed as) a single
+/// character. Use [`grid_cell!`](crate::grid_cell) to define an enum of cells.
+pub trait GridCell: Copy {
+    fn from_char(ch: char) -> Option<Self>;
+
+    fn to_char(self) -> char;
+}
+
+impl GridCell for char {
+    fn from_char(ch: char) -> Option<Self> {
+        Some(ch)
+    }
+
+    fn to_char(self) -> char {
+        self
+    }
+}
+
+/// Open (`.`) or blocked (`#`).
+impl GridCell for bool {
+    fn from_char(ch: char) -> Option<Self> {
+        match ch {
+            '.' => Some(true),
+            '#' => Some(false),
+            _ => None,
+        }
+    }
+
+    fn to_char(self) -> char {
+        if self { '.' } else { '#' }
+    }
+}
+
+/// Defines an enum of grid cells with the character of every variant, e.g.:
+///
+/// ```ignore
+/// grid_cell! {
+///     enum Warehouse {
+///         Wall = '#',
+///         Floor = '.',
+///         Crate = 'O' | 'o',
+///     }
+/// }
+/// let grid: Grid<Warehouse> = input.parse()?;
+/// ```
+///
+/// Alternative characters (after `|`) are accepted when parsing, the first one
+/// is used to print the cell.
+#[macro_export]
+macro_rules! grid_cell {
+    {
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($variant:ident = $ch:literal $(| $alt:literal)*),* $(,)?
+        }
+    } => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        $vis enum $name {
+            $($variant),*
+        }
+
+        impl $crate::GridCell for $name {
+            fn from_char(ch: char) -> Option<Self> {
+                match ch {
+                    $($ch $(| $alt)* => Some(Self::$variant),)*
+                    _ => None,
+                }
+            }
+
+            fn to_char(self) -> char {
+                match self {
+                    $(Self::$variant => $ch),*
+                }
+            }
+        }
+    };
+}
+
+impl<T> Grid<T> {
+    /// Parses a grid and maps every character to a cell with `f`. The
+    /// positions of the `markers` (e.g. `S` and `E`) are returned as well,
+    /// every marker has to occur exactly once. Markers are mapped with `f`
+    /// like any other character, so they can become floor.
+    pub fn parse_with_markers(
+        input: &str,
+        markers: &[char],
+        mut f: impl FnMut(char) -> T,
+    ) -> Result<(Self, HashMap<char, Pos2>)> {
+        let mut found: HashMap<char, Vec<Pos2>> = markers.iter().map(|&m| (m, vec![])).collect();
+        let grid = Self::parse_cells(input, |pos, ch| {
+            if let Some(positions) = found.get_mut(&ch) {
+                positions.push(pos);
+            }
+            Ok(f(ch))
+        })?;
+
+        let markers = found
+            .into_iter()
+            .map(|(marker, positions)| match positions[..] {
+                [pos] => Ok((marker, pos)),
+                [] => bail!("marker {marker:?} not found in the grid"),
+                _ => bail!(
+                    "marker {marker:?} found {} times in the grid, at {}",
+                    positions.len(),
+                    positions.iter().map(|p| format!("{p:?}")).join(", ")
+                ),
+            })
+            .try_collect()?;
+        Ok((grid, markers))
+    }
+
+    /// Parses every character (with its position) into a cell, all lines
+    /// should have the same length.
+    fn parse_cells(input: &str, mut f: impl FnMut(Pos2, char) -> Result<T>) -> Result<Self> {
+        let mut cells = vec![];
+        let mut columns = None;
+        let mut rows = 0;
+        for line in input.lines() {
+            let start = cells.len();
+            for (col, ch) in line.chars().enumerate() {
+                cells.push(f(Pos2(rows, col), ch)?);
+            }
+            let width = cells.len() - start;
+            let columns = *columns.get_or_insert(width);
+            ensure!(
+                width == columns,
+                "line {} has {width} columns, expected {columns}",
+                rows + 1
+            );
+            rows += 1;
+        }
+        ensure!(!cells.is_empty(), "expected a grid, found empty input");
+        Ok(Self(
+            Matrix::from_vec(rows, cells.len() / rows, cells).unwrap(),
+        ))
+    }
+}
+
 impl<T: Copy + PartialEq> Grid<T> {
     pub fn new(rows: usize, columns: usize, value: T) -> Self {
         Self(Matrix::new(rows, columns, value))
@@ -40,10 +202,14 @@ impl<T: Copy + PartialEq> Grid<T> {
         self.0.get(pos.try_into().ok()?.into()).copied()
     }
 
+    pub fn get_mut(&mut self, pos: impl TryInto<Pos2>) -> Option<&mut T> {
+        self.0.get_mut(pos.try_into().ok()?.into())
+    }
+
     pub fn positions(&self, ch: T) -> impl Iterator<Item = Pos2> + use<'_, T> {
         self.0
             .items()
-            .filter(move |(_, b)| (**b == ch))
+            .filter(move |(_, b)| **b == ch)
             .map(|(pos, _)| pos.into())
     }
 
@@ -119,23 +285,137 @@ impl<T: Copy + PartialEq> Grid<T> {
 }
 
 impl Grid<bool> {
+    /// The shortest path over open cells, see [`Grid::search`] for more
+    /// control.
     pub fn astar_flat(
         &self,
         start: Pos2,
         end: Pos2,
         neighbourhood: Neighbourhood,
     ) -> Option<(impl Iterator<Item = Pos2>, usize)> {
-        let (route, cost) = astar(
-            &start,
-            |&n| {
-                self.neighbours(n, neighbourhood)
-                    .filter(|(_, ok)| *ok)
-                    .map(|(p, _)| (p, 1))
-            },
-            |&n| n.abs_diff(end),
-            |&n| n == end,
-        )?;
-        Some((route.into_iter().map(Pos2::from), cost))
+        let (route, cost) = self
+            .search(|_, (_, open)| open.then_some(1))
+            .neighbourhood(neighbourhood)
+            .heuristic(|pos| pos.abs_diff(end))
+            .best(start, |pos| pos == end)?;
+        Some((route.into_iter(), cost))
+    }
+}
+
+/// The shared interface of [`Grid`] and [`SparseGrid`](crate::SparseGrid),
+/// so algorithms can be written once for both. Positions outside the grid (or
+/// without a cell in a sparse grid) have no cell.
+pub trait GridLike {
+    type Pos: Copy + Eq + Hash;
+    type Cell: Copy + PartialEq;
+
+    fn get(&self, pos: Self::Pos) -> Option<Self::Cell>;
+
+    /// The position `dir` away from `pos`, `None` if it cannot exist at all.
+    fn offset(&self, pos: Self::Pos, dir: Dir2) -> Option<Self::Pos>;
+
+    /// All cells (of a sparse grid only the ones that are stored).
+    fn items(&self) -> impl Iterator<Item = (Self::Pos, Self::Cell)>;
+
+    /// The smallest and largest position (inclusive) of all cells, `None`
+    /// when there are no cells.
+    fn bounds(&self) -> Option<(Self::Pos, Self::Pos)>;
+
+    fn step(&self, start: Self::Pos, dir: Dir2) -> Option<(Self::Pos, Self::Cell)> {
+        let pos = self.offset(start, dir)?;
+        Some((pos, self.get(pos)?))
+    }
+
+    /// Walks from `start` (included) in `dir` until there is no cell (an
+    /// infinite [`SparseGrid`](crate::SparseGrid) stops around its bounds).
+    fn walk(&self, start: Self::Pos, dir: Dir2) -> impl Iterator<Item = (Self::Pos, Self::Cell)> {
+        let first = self.get(start).map(|cell| (start, cell));
+        iter::successors(first, move |&(pos, _)| self.step(pos, dir))
+    }
+
+    fn neighbours(
+        &self,
+        pos: Self::Pos,
+        neighbourhood: Neighbourhood,
+    ) -> impl Iterator<Item = (Self::Pos, Self::Cell)> {
+        neighbourhood
+            .directions()
+            .iter()
+            .filter_map(move |&dir| self.step(pos, dir))
+    }
+
+    fn positions(&self, cell: Self::Cell) -> impl Iterator<Item = Self::Pos> {
+        self.items()
+            .filter(move |&(_, c)| c == cell)
+            .map(|(pos, _)| pos)
+    }
+
+    /// All positions that can be reached from `start` (included) through
+    /// neighbours for which `predicate` holds (an infinite
+    /// [`SparseGrid`](crate::SparseGrid) stops around its bounds).
+    fn bfs_reachable(
+        &self,
+        start: Self::Pos,
+        neighbourhood: Neighbourhood,
+        mut predicate: impl FnMut(Self::Pos, Self::Cell) -> bool,
+    ) -> impl Iterator<Item = Self::Pos> {
+        let reachable = bfs_reach(start, |&pos| {
+            self.neighbours(pos, neighbourhood)
+                .filter(|&(pos, cell)| predicate(pos, cell))
+                .map(|(pos, _)| pos)
+                .collect_vec()
+        });
+        reachable.collect_vec().into_iter()
+    }
+}
+
+impl<T: Copy + PartialEq> GridLike for Grid<T> {
+    type Pos = Pos2;
+    type Cell = T;
+
+    fn get(&self, pos: Pos2) -> Option<T> {
+        Grid::get(self, pos)
+    }
+
+    fn offset(&self, pos: Pos2, dir: Dir2) -> Option<Pos2> {
+        Some(Pos2(
+            pos.0.checked_add_signed(dir.0)?,
+            pos.1.checked_add_signed(dir.1)?,
+        ))
+    }
+
+    fn items(&self) -> impl Iterator<Item = (Pos2, T)> {
+        Grid::items(self)
+    }
+
+    fn bounds(&self) -> Option<(Pos2, Pos2)> {
+        let (rows, columns) = (self.rows(), self.columns());
+        (rows > 0 && columns > 0).then(|| (Pos2(0, 0), Pos2(rows - 1, columns - 1)))
+    }
+
+    fn step(&self, start: Pos2, dir: Dir2) -> Option<(Pos2, T)> {
+        Grid::step(self, start, dir)
+    }
+
+    fn walk(&self, start: Pos2, dir: Dir2) -> impl Iterator<Item = (Pos2, T)> {
+        Grid::walk(self, start, dir)
+    }
+
+    fn neighbours(
+        &self,
+        pos: Pos2,
+        neighbourhood: Neighbourhood,
+    ) -> impl Iterator<Item = (Pos2, T)> {
+        Grid::neighbours(self, pos, neighbourhood)
+    }
+
+    fn bfs_reachable(
+        &self,
+        start: Pos2,
+        neighbourhood: Neighbourhood,
+        predicate: impl FnMut(Pos2, T) -> bool,
+    ) -> impl Iterator<Item = Pos2> {
+        Grid::bfs_reachable(self, start, neighbourhood, predicate)
     }
 }
 
@@ -170,23 +450,29 @@ impl<T> IndexMut<Pos2> for Grid<T> {
     }
 }
 
-impl Debug for Grid<char> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in &self.0 {
-            for &ch in row {
-                f.write_char(ch)?
-            }
-            f.write_char('\n')?
-        }
-        Ok(())
+impl<T: GridCell> FromStr for Grid<T> {
+    type Err = Report;
+
+    /// Parses a grid of [`GridCell`]s, unknown characters are reported with
+    /// their line and column.
+    fn from_str(input: &str) -> Result<Self> {
+        Self::parse_cells(input, |pos, ch| {
+            T::from_char(ch).ok_or_else(|| {
+                eyre!(
+                    "unexpected character {ch:?} at line {}, column {}",
+                    pos.0 + 1,
+                    pos.1 + 1
+                )
+            })
+        })
     }
 }
 
-impl Debug for Grid<bool> {
+impl<T: GridCell> Debug for Grid<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for row in &self.0 {
-            for &b in row {
-                f.write_char(if b { '.' } else { '#' })?
+            for &cell in row {
+                f.write_char(cell.to_char())?
             }
             f.write_char('\n')?
         }
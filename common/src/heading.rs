@@ -0,0 +1,227 @@
+use crate::{Dir2, E, GridCell, Itertools, N, NE, NW, Result, S, SE, SW, W, eyre};
+use color_eyre::Report;
+use std::str::FromStr;
+
+/// One of the eight compass directions, in clockwise order from north. Unlike
+/// a [`Dir2`] a heading is always a unit step, so it can be turned and used
+/// as an index, e.g. to keep the headings a cell was visited with as a bitset:
+///
+/// ```ignore
+/// let first_visit = seen[pos] & heading.bit() == 0;
+/// seen[pos] |= heading.bit();
+/// ```
+///
+/// Parsed from (and printed as) an arrow with [`GridCell`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, PartialOrd, Ord)]
+pub enum Heading {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Heading {
+    /// All headings, clockwise from north.
+    pub const ALL: [Self; 8] = [
+        Self::N,
+        Self::NE,
+        Self::E,
+        Self::SE,
+        Self::S,
+        Self::SW,
+        Self::W,
+        Self::NW,
+    ];
+
+    /// The four cardinal headings, clockwise from north.
+    pub const CARDINAL: [Self; 4] = [Self::N, Self::E, Self::S, Self::W];
+
+    /// The position in [`Heading::ALL`] (`0..8`).
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// A single bit for a bitset of headings, see [`Heading::index`].
+    pub fn bit(self) -> u8 {
+        1 << self.index()
+    }
+
+    pub fn dir(self) -> Dir2 {
+        match self {
+            Self::N => N,
+            Self::NE => NE,
+            Self::E => E,
+            Self::SE => SE,
+            Self::S => S,
+            Self::SW => SW,
+            Self::W => W,
+            Self::NW => NW,
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    /// Rotate by `eighths` times 45°, clockwise when positive.
+    pub fn rotate(self, eighths: isize) -> Self {
+        Self::ALL[(self.index() as isize + eighths).rem_euclid(8) as usize]
+    }
+
+    /// Rotate 45° clockwise
+    pub fn rotate_45_cw(self) -> Self {
+        self.rotate(1)
+    }
+
+    /// Rotate 45° counter clockwise
+    pub fn rotate_45_ccw(self) -> Self {
+        self.rotate(-1)
+    }
+
+    /// Rotate 90° clockwise
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Rotate 90° counter clockwise
+    pub fn turn_left(self) -> Self {
+        self.rotate(-2)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.rotate(4)
+    }
+
+    /// The number of 45° turns to face `other` (at most 4).
+    pub fn turns_to(self, other: Self) -> usize {
+        let turns = self.index().abs_diff(other.index());
+        turns.min(8 - turns)
+    }
+
+    /// The arrow pointing this way, e.g. `↗` for north-east.
+    pub fn arrow(self) -> char {
+        match self {
+            Self::N => '↑',
+            Self::NE => '↗',
+            Self::E => '→',
+            Self::SE => '↘',
+            Self::S => '↓',
+            Self::SW => '↙',
+            Self::W => '←',
+            Self::NW => '↖',
+        }
+    }
+}
+
+/// Parses `^>v<`, `NESW` and the arrows of [`Heading::arrow`]. Cardinal
+/// headings are printed as `^>v<`, diagonal ones as arrows.
+impl GridCell for Heading {
+    fn from_char(ch: char) -> Option<Self> {
+        match ch {
+            '^' | 'N' => Some(Self::N),
+            '>' | 'E' => Some(Self::E),
+            'v' | 'S' => Some(Self::S),
+            '<' | 'W' => Some(Self::W),
+            _ => Self::ALL.into_iter().find(|h| h.arrow() == ch),
+        }
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Self::N => '^',
+            Self::E => '>',
+            Self::S => 'v',
+            Self::W => '<',
+            _ => self.arrow(),
+        }
+    }
+}
+
+impl FromStr for Heading {
+    type Err = Report;
+
+    /// A single character (see [`GridCell`]) or a compass point such as `NE`.
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "NE" => Some(Self::NE),
+            "SE" => Some(Self::SE),
+            "SW" => Some(Self::SW),
+            "NW" => Some(Self::NW),
+            _ => s.chars().exactly_one().ok().and_then(Self::from_char),
+        }
+        .ok_or_else(|| eyre!("not a heading: {s:?}"))
+    }
+}
+
+impl From<Heading> for Dir2 {
+    fn from(heading: Heading) -> Self {
+        heading.dir()
+    }
+}
+
+impl TryFrom<Dir2> for Heading {
+    type Error = Report;
+
+    fn try_from(dir: Dir2) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|h| h.dir() == dir)
+            .ok_or_else(|| eyre!("not a unit direction: {dir:?}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Heading::{self, *};
+    use crate::{Dir2, GridCell};
+
+    #[test]
+    fn rotates_and_counts_turns() {
+        assert_eq!(N.rotate(3), SE);
+        assert_eq!(N.rotate(-1), NW);
+        assert_eq!(W.rotate(10), N);
+        assert_eq!(NE.rotate(-17), N);
+        assert_eq!((E.turn_left(), E.turn_right(), E.reverse()), (N, S, W));
+        for heading in Heading::ALL {
+            assert_eq!(heading.rotate_45_cw().rotate_45_ccw(), heading);
+            assert_eq!(heading.turns_to(heading.reverse()), 4);
+            assert_eq!(heading.dir().rotate_90_cw(), heading.turn_right().dir());
+        }
+        assert_eq!(
+            (
+                N.turns_to(N),
+                N.turns_to(NW),
+                NW.turns_to(NE),
+                E.turns_to(W)
+            ),
+            (0, 1, 2, 4)
+        );
+        assert_eq!(SW.turns_to(NE), 4);
+        assert_eq!(NW.turns_to(E), 3);
+    }
+
+    #[test]
+    fn converts_to_cells_and_directions() {
+        for heading in Heading::ALL {
+            assert_eq!(Heading::from_char(heading.to_char()), Some(heading));
+            assert_eq!(Heading::from_char(heading.arrow()), Some(heading));
+            assert_eq!(format!("{heading:?}").parse::<Heading>().unwrap(), heading);
+            assert_eq!(Heading::try_from(heading.dir()).unwrap(), heading);
+        }
+        assert_eq!(
+            Heading::CARDINAL.map(Heading::to_char),
+            ['^', '>', 'v', '<']
+        );
+        assert_eq!(
+            ["N", "NE", "v", "↙"].map(|s| s.parse::<Heading>().unwrap()),
+            [N, NE, S, SW]
+        );
+        assert_eq!(Heading::from_char('x'), None);
+        assert!("NNE".parse::<Heading>().is_err());
+        assert!(Heading::try_from(Dir2(2, 0)).is_err());
+    }
+}
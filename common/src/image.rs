@@ -0,0 +1,300 @@
+//! Grids as image files (PPM or PNG) and sequences of grids as animated GIFs,
+//! e.g. to attach the state of a simulation to a review or a bug report.
+
+use crate::{Color, Grid, Pos2, Result, WrapErr, bail, ensure};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Self = Self(0, 0, 0);
+    pub const WHITE: Self = Self(255, 255, 255);
+    pub const GREY: Self = Self(128, 128, 128);
+}
+
+impl From<Color> for Rgb {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::Red => Self(220, 50, 47),
+            Color::Green => Self(64, 160, 43),
+            Color::Yellow => Self(223, 142, 29),
+            Color::Blue => Self(30, 102, 245),
+            Color::Magenta => Self(136, 57, 239),
+            Color::Cyan => Self(4, 165, 229),
+        }
+    }
+}
+
+/// A grid as an image, with one square of `scale` pixels per cell. Created
+/// with [`Grid::image`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    rows: usize,
+    columns: usize,
+    cells: Vec<Rgb>,
+    scale: usize,
+}
+
+impl<T: Copy + PartialEq> Grid<T> {
+    /// Paints every cell with the colour that `palette` gives it.
+    pub fn image(&self, palette: impl Fn(T) -> Rgb) -> Image {
+        Image {
+            rows: self.rows(),
+            columns: self.columns(),
+            cells: self.row_iter().flatten().map(|&c| palette(c)).collect(),
+            scale: 1,
+        }
+    }
+}
+
+impl Image {
+    /// Use `scale` by `scale` pixels for every cell.
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Paints the given positions (e.g. a path) on top of the cells.
+    pub fn overlay(
+        mut self,
+        positions: impl IntoIterator<Item = Pos2>,
+        color: impl Into<Rgb>,
+    ) -> Self {
+        let color = color.into();
+        for Pos2(row, col) in positions {
+            if row < self.rows && col < self.columns {
+                self.cells[row * self.columns + col] = color;
+            }
+        }
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.columns * self.scale
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows * self.scale
+    }
+
+    /// All pixels, row by row.
+    fn pixels(&self) -> impl Iterator<Item = Rgb> + '_ {
+        let scale = self.scale;
+        self.cells.chunks(self.columns.max(1)).flat_map(move |row| {
+            let row = row.iter().flat_map(move |&c| std::iter::repeat_n(c, scale));
+            std::iter::repeat_n(row, scale).flatten()
+        })
+    }
+
+    fn rgb_bytes(&self) -> Vec<u8> {
+        self.pixels().flat_map(|Rgb(r, g, b)| [r, g, b]).collect()
+    }
+
+    /// Writes a binary PPM (`P6`) image.
+    pub fn write_ppm(&self, mut out: impl Write) -> Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width(), self.height())?;
+        out.write_all(&self.rgb_bytes())?;
+        Ok(())
+    }
+
+    pub fn write_png(&self, out: impl Write) -> Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width() as u32, self.height() as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.rgb_bytes())?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Saves the image as PPM or PNG, depending on the extension of `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let out = create(path)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("ppm") => self.write_ppm(out),
+            Some("png") => self.write_png(out),
+            _ => bail!("unsupported image format (use .ppm or .png): {path:?}"),
+        }
+        .wrap_err_with(|| format!("could not write {path:?}"))
+    }
+}
+
+fn create(path: &Path) -> Result<BufWriter<File>> {
+    let file = File::create(path).wrap_err_with(|| format!("could not create {path:?}"))?;
+    Ok(BufWriter::new(file))
+}
+
+/// Writes a sequence of images as an animated GIF that loops forever, e.g.:
+///
+/// ```no_run
+/// # use common::*;
+/// # fn states() -> Vec<Grid<bool>> { vec![] }
+/// let mut gif = GifWriter::create("robots.gif", std::time::Duration::from_millis(100));
+/// for grid in states() {
+///     gif.frame(&grid.image(|open| if open { Rgb::WHITE } else { Rgb::BLACK }))?;
+/// }
+/// gif.finish()?;
+/// # Ok::<_, color_eyre::Report>(())
+/// ```
+///
+/// All frames must have the same size and together use at most 256 colours.
+pub struct GifWriter {
+    path: PathBuf,
+    delay: Duration,
+    encoder: Option<gif::Encoder<BufWriter<File>>>,
+    size: (usize, usize),
+    palette: HashMap<Rgb, u8>,
+}
+
+impl GifWriter {
+    /// Shows every frame for `delay` (rounded to hundredths of a second). The
+    /// file is created when the first frame is written.
+    pub fn create(path: impl Into<PathBuf>, delay: Duration) -> Self {
+        Self {
+            path: path.into(),
+            delay,
+            encoder: None,
+            size: (0, 0),
+            palette: HashMap::new(),
+        }
+    }
+
+    pub fn frame(&mut self, image: &Image) -> Result<()> {
+        self.write_frame(image)
+            .wrap_err_with(|| format!("could not write {:?}", self.path))
+    }
+
+    fn write_frame(&mut self, image: &Image) -> Result<()> {
+        let size = (image.width(), image.height());
+        let encoder = match &mut self.encoder {
+            Some(encoder) => {
+                ensure!(
+                    size == self.size,
+                    "frame of {}x{} pixels in a GIF of {}x{}",
+                    size.0,
+                    size.1,
+                    self.size.0,
+                    self.size.1
+                );
+                encoder
+            }
+            None => {
+                ensure!(
+                    size.0 <= u16::MAX as usize && size.1 <= u16::MAX as usize,
+                    "frame of {}x{} pixels is too large for a GIF",
+                    size.0,
+                    size.1
+                );
+                let out = create(&self.path)?;
+                let mut encoder = gif::Encoder::new(out, size.0 as u16, size.1 as u16, &[])?;
+                encoder.set_repeat(gif::Repeat::Infinite)?;
+                self.size = size;
+                self.encoder.insert(encoder)
+            }
+        };
+
+        // Every frame has a local palette with the colours used so far, grids
+        // rarely need more than a few.
+        let mut indices = Vec::with_capacity(size.0 * size.1);
+        for pixel in image.pixels() {
+            let index = match self.palette.get(&pixel) {
+                Some(&index) => index,
+                None => {
+                    let index = self.palette.len();
+                    ensure!(index < 256, "more than 256 colours in a GIF");
+                    self.palette.insert(pixel, index as u8);
+                    index as u8
+                }
+            };
+            indices.push(index);
+        }
+        let mut palette = vec![0; self.palette.len() * 3];
+        for (&Rgb(r, g, b), &index) in &self.palette {
+            palette[index as usize * 3..][..3].copy_from_slice(&[r, g, b]);
+        }
+
+        let mut frame =
+            gif::Frame::from_palette_pixels(size.0 as u16, size.1 as u16, indices, palette, None);
+        frame.delay = (self.delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+        encoder.write_frame(&frame)?;
+        Ok(())
+    }
+
+    /// Finishes the file, a GIF without frames is an error.
+    pub fn finish(self) -> Result<()> {
+        let Some(encoder) = self.encoder else {
+            bail!("no frames for {:?}", self.path);
+        };
+        let mut out = encoder
+            .into_inner()
+            .wrap_err_with(|| format!("could not write {:?}", self.path))?;
+        out.flush()
+            .wrap_err_with(|| format!("could not write {:?}", self.path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs, process};
+
+    fn image() -> Image {
+        let grid: Grid<bool> = ".#\n..".parse().unwrap();
+        let palette = |open| if open { Rgb::WHITE } else { Rgb::BLACK };
+        grid.image(palette)
+            .overlay([Pos2(1, 1), Pos2(5, 5)], Color::Red)
+    }
+
+    #[test]
+    fn writes_scaled_pixels() {
+        let mut ppm = vec![];
+        image().scale(2).write_ppm(&mut ppm).unwrap();
+        let (header, pixels) = ppm.split_at(11);
+        assert_eq!(header, b"P6\n4 4\n255\n");
+        let (w, b, r) = ([255; 3], [0; 3], [220, 50, 47]);
+        let row = |a: [u8; 3], b: [u8; 3]| [a, a, b, b];
+        let expected = [row(w, b), row(w, b), row(w, r), row(w, r)]
+            .concat()
+            .concat();
+        assert_eq!(pixels, expected);
+
+        let mut png = vec![];
+        image().write_png(&mut png).unwrap();
+        let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        assert_eq!(pixels, [w, b, w, r].concat());
+    }
+
+    #[test]
+    fn writes_an_animation() {
+        let path = env::temp_dir().join(format!("gif-writer-{}.gif", process::id()));
+        let mut gif = GifWriter::create(&path, Duration::from_millis(250));
+        gif.frame(&image()).unwrap();
+        gif.frame(&image().overlay([Pos2(0, 0)], Rgb::GREY))
+            .unwrap();
+        assert!(gif.frame(&image().scale(2)).is_err());
+        gif.finish().unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(fs::File::open(&path).unwrap()).unwrap();
+        let mut frames = vec![];
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!((frame.width, frame.height, frame.delay), (2, 2, 25));
+            frames.push(frame.buffer.chunks(4).map(|p| p[0]).collect::<Vec<_>>());
+        }
+        fs::remove_file(&path).unwrap();
+        assert_eq!(frames, [[255, 0, 255, 220], [128, 0, 255, 220]]);
+
+        assert!(GifWriter::create(&path, Duration::ZERO).finish().is_err());
+    }
+}
@@ -1,20 +1,58 @@
 use std::sync::Once;
 
-pub use color_eyre::Result;
+pub use animate::*;
+pub use answers::*;
+pub use check::*;
+pub use color_eyre::{
+    Result,
+    eyre::{WrapErr, bail, ensure, eyre},
+};
+pub use components::*;
+pub use connectivity::*;
 pub use grid::*;
+pub use heading::*;
+pub use image::*;
 pub use itertools::*;
+pub use parse::*;
 pub use paste::paste;
+pub use pattern::*;
 pub use positioning::*;
 pub use rayon::prelude::*;
+pub use render::*;
+pub use report::*;
+pub use runner::*;
+pub use search::*;
+pub use sparse::*;
+pub use wrapping::*;
 
 pub type SS = &'static str;
 
+/// Signature of a solver, `R` can also be a [`Result`] for solvers that can
+/// fail, see [`Answer`].
 pub type Solution<R = usize> = fn(SS) -> R;
 
 pub const CLEAR_TERM: &str = "\x1b[2J\x1b[H";
 
+mod animate;
+mod answers;
+pub mod bench;
+mod check;
+mod components;
+mod connectivity;
+mod distance;
 mod grid;
+mod heading;
+mod image;
+mod parse;
+mod pattern;
 mod positioning;
+mod render;
+mod report;
+mod runner;
+mod search;
+mod sparse;
+mod transform;
+mod wrapping;
 
 pub mod pathfinding {
     pub use pathfinding::prelude::*;
@@ -26,10 +64,12 @@ pub fn init() {
     INIT.call_once(|| color_eyre::install().unwrap());
 }
 
+/// Panics on invalid input, see [`try_usize`] for a fallible variant.
 pub fn to_usize(input: impl AsRef<str>) -> usize {
     input.as_ref().parse().unwrap()
 }
 
+/// Panics on invalid input, see [`try_isize`] for a fallible variant.
 pub fn to_isize(input: impl AsRef<str>) -> isize {
     input.as_ref().parse().unwrap()
 }
@@ -46,31 +86,81 @@ pub fn swap<A, B>(tup: (A, B)) -> (B, A) {
     (tup.1, tup.0)
 }
 
+/// Generates `main`, the list of `CASES` and a test for every case, e.g.:
+///
+/// ```ignore
+/// boilerplate! {
+///     part1 => { test(11, 7) -> 12, #[optional] real(101, 103) -> 220971520 }
+///     part2 => { #[ignore] #[optional] real -> 6355, #[should_fail] broken }
+/// }
+/// ```
+///
+/// Every input is read from `<input>.txt` (or given inline as a literal).
+/// Without an expected value, the tests compare with the answer in
+/// `answers.toml` next to the input (see [`Answers`]), which is written by
+/// running the day with `--bless`. Cases can be marked with `#[ignore]`
+/// (slow), `#[should_fail]` (the solver should panic or return an error, the
+/// expected value can be omitted) and `#[optional]` (skipped when the input is
+/// missing), see [`Attrs`].
 #[macro_export]
 macro_rules! boilerplate {
     {
-        $($name:ident => { $($input:ident$(($($p:expr),*))? -> $value:expr),* $(,)? })*
+        $($name:ident => {
+            $($(#[$attr:ident])* $input:ident$(($($p:expr),*))? $(-> $value:expr)?),* $(,)?
+        })*
     } => {
-        fn main() {
+        #[cfg(not(aoc_runner))]
+        const DAY: $crate::Day = $crate::Day {
+            name: env!("CARGO_PKG_NAME"),
+            dir: concat!(env!("CARGO_MANIFEST_DIR"), "/src"),
+            cases: CASES,
+        };
+
+        #[cfg(not(aoc_runner))]
+        fn main() -> $crate::Result<()> {
             $crate::init();
-            $($({
-                let input = include_str!(concat!(stringify!($input), ".txt"));
-                println!(concat!("Result of ", stringify!($name), ", ", stringify!($input), ": {}"), $name(input $(, $($p),*)?));
-            })*)*
+            $crate::run_day(DAY)
         }
 
-        #[cfg(test)]
+        pub static CASES: &[$crate::Case] = &[$($(
+            $crate::Case {
+                part: stringify!($name),
+                input: stringify!($input),
+                params: stringify!($($($p),*)?),
+                inline: None,
+                solve: |input| {
+                    $crate::Answer::into_result($name(input $(, $($p),*)?)).map(|v| v.to_string())
+                },
+                attrs: $crate::__attrs!($($attr)*),
+                expected: $crate::__expected!(|input| $name(input $(, $($p),*)?); $($value)?),
+            },
+        )*)*];
+
+        #[cfg(all(test, not(aoc_runner)))]
         mod tests {
             use super::*;
             use $crate::paste;
 
             $($(
                 paste!{
-                    #[test]
-                    fn [<$name _ $input $(_ $($p:lower)_*)?>]() {
-                        $crate::init();
-                        let input = include_str!(concat!(stringify!($input), ".txt"));
-                        assert_eq!($name(input $(, $($p),*)?), $value);
+                    $crate::__test_attrs! {
+                        [$($attr)*]
+                        #[test]
+                        fn [<$name _ $input $(_ $($p:lower)_*)?>]() -> $crate::Result<()> {
+                            $crate::init();
+                            let case = DAY.case(
+                                stringify!($name),
+                                stringify!($input),
+                                stringify!($($($p),*)?),
+                            );
+                            $crate::run_test(
+                                &DAY,
+                                case,
+                                $crate::__embedded!([$($attr)*] $input),
+                                |input| $name(input $(, $($p),*)?),
+                                $crate::__check!($($value)?),
+                            )
+                        }
                     }
                 }
             )*)*
@@ -78,26 +168,50 @@ macro_rules! boilerplate {
         }
     };
     {
-        $($name:ident => { $($input:literal -> $value:expr),* $(,)? })*
+        $($name:ident => {
+            $($(#[$attr:ident])* $input:literal $(-> $value:expr)?),* $(,)?
+        })*
     } => {
-        fn main() {
+        #[cfg(not(aoc_runner))]
+        const DAY: $crate::Day = $crate::Day {
+            name: env!("CARGO_PKG_NAME"),
+            dir: concat!(env!("CARGO_MANIFEST_DIR"), "/src"),
+            cases: CASES,
+        };
+
+        #[cfg(not(aoc_runner))]
+        fn main() -> $crate::Result<()> {
             $crate::init();
-            $($({
-                println!(concat!("Result of ", stringify!($name), ", ", stringify!($input), ": {}"), $name($input));
-            })*)*
+            $crate::run_day(DAY)
         }
 
-        #[cfg(test)]
+        pub static CASES: &[$crate::Case] = &[$($(
+            $crate::Case {
+                part: stringify!($name),
+                input: stringify!($input),
+                params: "",
+                inline: Some($input),
+                solve: |input| $crate::Answer::into_result($name(input)).map(|v| v.to_string()),
+                attrs: $crate::__attrs!($($attr)*),
+                expected: $crate::__expected!($name; $($value)?),
+            },
+        )*)*];
+
+        #[cfg(all(test, not(aoc_runner)))]
         mod tests {
             use super::*;
             use $crate::paste;
 
             $($(
                 paste!{
-                    #[test]
-                    fn [<$name _ $input>]() {
-                        $crate::init();
-                        assert_eq!($name($input), $value);
+                    $crate::__test_attrs! {
+                        [$($attr)*]
+                        #[test]
+                        fn [<$name _ $input>]() -> $crate::Result<()> {
+                            $crate::init();
+                            let case = DAY.case(stringify!($name), stringify!($input), "");
+                            $crate::run_test(&DAY, case, None, $name, $crate::__check!($($value)?))
+                        }
                     }
                 }
             )*)*
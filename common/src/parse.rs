@@ -0,0 +1,288 @@
+//! Fallible parsing helpers for the shapes that keep coming back in the
+//! inputs. Errors contain the offending text and point at the line (and
+//! column) where parsing failed. Line numbers count from the start of the
+//! input, also within a [`Section`].
+
+use crate::{Result, SS, WrapErr, bail, eyre};
+use std::{any::type_name, error::Error, ops::Deref, str::FromStr};
+
+/// Parses `text` (surrounding whitespace is ignored), the error contains the
+/// offending text.
+pub fn try_parse<T>(text: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    text.trim()
+        .parse()
+        .wrap_err_with(|| format!("invalid {}: {text:?}", type_name::<T>()))
+}
+
+/// Fallible version of [`to_usize`](crate::to_usize).
+pub fn try_usize(text: impl AsRef<str>) -> Result<usize> {
+    try_parse(text.as_ref())
+}
+
+/// Fallible version of [`to_isize`](crate::to_isize).
+pub fn try_isize(text: impl AsRef<str>) -> Result<isize> {
+    try_parse(text.as_ref())
+}
+
+/// Parses a single line of values separated by `sep`, e.g. `75,47,61,53`.
+/// Errors point at the column of the offending value.
+pub fn separated<T>(line: &str, sep: char) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    let mut col = 1;
+    line.split(sep)
+        .map(|value| {
+            let at = col;
+            col += value.chars().count() + 1;
+            try_parse(value).wrap_err_with(|| format!("at column {at}"))
+        })
+        .collect()
+}
+
+/// Parses a single line of comma-separated values, see [`separated`].
+pub fn comma_separated<T>(line: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    separated(line, ',')
+}
+
+/// A part of the input that knows the number of its first line, so that
+/// [`parse_lines`] reports lines of the whole input. A plain `&str` is a
+/// section that starts at line 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section {
+    pub text: SS,
+    pub first_line: usize,
+}
+
+impl From<SS> for Section {
+    fn from(text: SS) -> Self {
+        Self {
+            text,
+            first_line: 1,
+        }
+    }
+}
+
+impl Deref for Section {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.text
+    }
+}
+
+/// Splits the input into sections that are separated by blank lines, empty
+/// sections (e.g. because of trailing newlines) are skipped.
+pub fn sections(input: SS) -> impl Iterator<Item = Section> {
+    input
+        .split("\n\n")
+        .map(|section| section.trim_matches('\n'))
+        .filter(|section| !section.is_empty())
+        .map(move |text| {
+            // every section is a slice of the input
+            let offset = text.as_ptr() as usize - input.as_ptr() as usize;
+            Section {
+                text,
+                first_line: input[..offset].matches('\n').count() + 1,
+            }
+        })
+}
+
+/// Splits the input into exactly `N` sections, see [`sections`].
+pub fn split_sections<const N: usize>(input: SS) -> Result<[Section; N]> {
+    let found = sections(input).collect::<Vec<_>>();
+    let count = found.len();
+    found
+        .try_into()
+        .map_err(|_| eyre!("expected {N} sections separated by blank lines, found {count}"))
+}
+
+/// Parses every line of `text` (the input or a [`Section`] of it) with `f`,
+/// errors are wrapped with the line number and the line itself.
+pub fn parse_lines<T>(
+    text: impl Into<Section>,
+    mut f: impl FnMut(SS) -> Result<T>,
+) -> impl Iterator<Item = Result<T>> {
+    let Section { text, first_line } = text.into();
+    text.lines().enumerate().map(move |(i, line)| {
+        f(line).wrap_err_with(|| format!("on line {}: {line:?}", first_line + i))
+    })
+}
+
+/// Splits a `key: value` line.
+pub fn key_value(line: SS) -> Result<(SS, SS)> {
+    let Some((key, value)) = line.split_once(':') else {
+        bail!("expected `<key>: <value>`, found {line:?}");
+    };
+    Ok((key.trim(), value.trim()))
+}
+
+/// Splits every line of `text` as a `key: value` pair, see [`key_value`].
+pub fn key_values(text: impl Into<Section>) -> impl Iterator<Item = Result<(SS, SS)>> {
+    parse_lines(text, key_value)
+}
+
+/// Extracts the values of the `{}` placeholders in `template` from `text` and
+/// parses them as the fields of `T` (a tuple), e.g.
+/// `scan::<(isize, isize, isize, isize)>("p={},{} v={},{}", line)`.
+///
+/// Everything outside the placeholders has to match literally, a placeholder
+/// ends at the first occurrence of the text that follows it in the template.
+pub fn scan<T: Record>(template: &str, text: &str) -> Result<T> {
+    let mut literals = template.split("{}");
+    let first = literals.next().unwrap_or_default();
+    let literals = literals.collect::<Vec<_>>();
+    if literals.len() != T::FIELDS {
+        bail!(
+            "template {template:?} has {} placeholders, expected {}",
+            literals.len(),
+            T::FIELDS
+        );
+    }
+
+    let mismatch = |rest: &str, expected: String| {
+        let col = text[..text.len() - rest.len()].chars().count() + 1;
+        eyre!("expected {expected} at column {col} of {text:?} (template {template:?})")
+    };
+    let mut rest = text
+        .strip_prefix(first)
+        .ok_or_else(|| mismatch(text, format!("{first:?}")))?;
+    let mut fields = Vec::with_capacity(T::FIELDS);
+    for (i, &literal) in literals.iter().enumerate() {
+        let end = match literal {
+            "" if i + 1 == literals.len() => rest.len(),
+            "" => bail!("template {template:?} has adjacent placeholders"),
+            _ => rest
+                .find(literal)
+                .ok_or_else(|| mismatch(rest, format!("{literal:?}")))?,
+        };
+        fields.push(&rest[..end]);
+        rest = &rest[end + literal.len()..];
+    }
+    if !rest.is_empty() {
+        return Err(mismatch(rest, "end of text".into()));
+    }
+    T::from_fields(&fields)
+}
+
+/// A tuple of values that can be parsed from text, see [`scan`].
+pub trait Record: Sized {
+    const FIELDS: usize;
+
+    fn from_fields(fields: &[&str]) -> Result<Self>;
+}
+
+macro_rules! impl_record {
+    ($($t:ident)*) => {
+        impl<$($t),*> Record for ($($t,)*)
+        where
+            $($t: FromStr, $t::Err: Error + Send + Sync + 'static),*
+        {
+            const FIELDS: usize = [$(stringify!($t)),*].len();
+
+            fn from_fields(fields: &[&str]) -> Result<Self> {
+                let mut fields = fields.iter().enumerate();
+                Ok(($({
+                    let (i, field) = fields.next().unwrap();
+                    try_parse::<$t>(field).wrap_err_with(|| format!("in field {}", i + 1))?
+                },)*))
+            }
+        }
+    };
+}
+
+impl_record!(A);
+impl_record!(A B);
+impl_record!(A B C);
+impl_record!(A B C D);
+impl_record!(A B C D E);
+impl_record!(A B C D E F);
+impl_record!(A B C D E F G);
+impl_record!(A B C D E F G H);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sections_report_lines_of_the_input() {
+        let input = "\n1\n2\n\n\n3\n\nx\n\n";
+        let found = sections(input)
+            .map(|s| (s.text, s.first_line))
+            .collect::<Vec<_>>();
+        assert_eq!(found, [("1\n2", 2), ("3", 6), ("x", 8)]);
+
+        let [_, second, third] = split_sections(input).unwrap();
+        let lines = parse_lines(second, try_usize)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(lines, [3]);
+        let err = parse_lines(third, try_usize).next().unwrap().unwrap_err();
+        assert_eq!(err.to_string(), r#"on line 8: "x""#);
+
+        let err = split_sections::<2>(input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected 2 sections separated by blank lines, found 3"
+        );
+    }
+
+    #[test]
+    fn scan_extracts_placeholders() {
+        let (px, py, vx, vy) =
+            scan::<(isize, isize, isize, isize)>("p={},{} v={},{}", "p=0,4 v=3,-3").unwrap();
+        assert_eq!((px, py, vx, vy), (0, 4, 3, -3));
+
+        let (name, value) = scan::<(char, usize)>("Register {}: {}", "Register A: 729").unwrap();
+        assert_eq!((name, value), ('A', 729));
+
+        // a placeholder ends at the first occurrence of the next literal
+        let (a, b) = scan::<(String, String)>("{}-{}", "a-b-c").unwrap();
+        assert_eq!((a.as_str(), b.as_str()), ("a", "b-c"));
+    }
+
+    #[test]
+    fn scan_reports_mismatches() {
+        let err = scan::<(usize,)>("x={};", "x=1,").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"expected ";" at column 3 of "x=1," (template "x={};")"#
+        );
+
+        let err = scan::<(usize,)>("x={}", "y=1").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"expected "x=" at column 1 of "y=1" (template "x={}")"#
+        );
+
+        let err = scan::<(usize,)>("({})", "(1) ").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"expected end of text at column 4 of "(1) " (template "({})")"#
+        );
+
+        let err = scan::<(usize, usize)>("{},{}", "1,x").unwrap_err();
+        assert_eq!(err.to_string(), "in field 2");
+
+        let err = scan::<(usize, usize)>("{}", "1").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"template "{}" has 1 placeholders, expected 2"#
+        );
+
+        let err = scan::<(usize, usize)>("{}{}", "12").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"template "{}{}" has adjacent placeholders"#
+        );
+    }
+}
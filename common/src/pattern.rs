@@ -0,0 +1,137 @@
+use crate::{Grid, Pos2, Result};
+use std::iter;
+
+/// A small grid to search for in a bigger one, see [`Grid::find_pattern`].
+/// Cells that are `None` are wildcards, they match anything.
+#[derive(PartialEq, Eq)]
+pub struct Pattern<T> {
+    grid: Grid<Option<T>>,
+    /// The cells that have to match, relative to the top left corner.
+    cells: Vec<(Pos2, T)>,
+}
+
+impl<T: Copy> Clone for Pattern<T> {
+    fn clone(&self) -> Self {
+        Self {
+            grid: self.grid.clone(),
+            cells: self.cells.clone(),
+        }
+    }
+}
+
+impl<T: Copy + PartialEq> Pattern<T> {
+    pub fn new(grid: Grid<Option<T>>) -> Self {
+        let cells = grid
+            .items()
+            .filter_map(|(pos, cell)| Some((pos, cell?)))
+            .collect();
+        Self { grid, cells }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.grid.rows()
+    }
+
+    pub fn columns(&self) -> usize {
+        self.grid.columns()
+    }
+
+    /// The distinct rotations and reflections of the pattern (including
+    /// itself), at most 8.
+    pub fn orientations(&self) -> Vec<Self> {
+        let mut orientations: Vec<Self> = vec![];
+        let mut grid = self.grid.clone();
+        for _ in 0..4 {
+            for grid in [grid.clone(), grid.flip_horizontal()] {
+                if !orientations.iter().any(|o| o.grid == grid) {
+                    orientations.push(Self::new(grid));
+                }
+            }
+            grid = grid.rotate_cw();
+        }
+        orientations
+    }
+
+    /// Whether the pattern matches with its top left corner at `pos`.
+    pub fn matches_at(&self, grid: &Grid<T>, pos: Pos2) -> bool {
+        pos.0 + self.rows() <= grid.rows()
+            && pos.1 + self.columns() <= grid.columns()
+            && self
+                .cells
+                .iter()
+                .all(|&(offset, cell)| grid[pos + offset] == cell)
+    }
+}
+
+impl Pattern<char> {
+    /// Parses a pattern of characters, `wildcard` matches any character, e.g.
+    /// `Pattern::parse("M.S\n.A.\nM.S", '.')`.
+    pub fn parse(text: &str, wildcard: char) -> Result<Self> {
+        let grid: Grid<char> = text.parse()?;
+        Ok(Self::new(grid.map(|&c| (c != wildcard).then_some(c))))
+    }
+}
+
+impl<T: Copy + PartialEq> Grid<T> {
+    /// The positions (of the top left corner) where `pattern` matches.
+    pub fn find_pattern<'a>(
+        &'a self,
+        pattern: &'a Pattern<T>,
+    ) -> impl Iterator<Item = Pos2> + use<'a, T> {
+        self.keys().filter(|&pos| pattern.matches_at(self, pos))
+    }
+
+    /// Like [`find_pattern`](Self::find_pattern), for every orientation of
+    /// `pattern` (see [`Pattern::orientations`]). A position is returned once
+    /// for every orientation that matches there.
+    pub fn find_pattern_oriented<'a>(
+        &'a self,
+        pattern: &Pattern<T>,
+    ) -> impl Iterator<Item = Pos2> + use<'a, T> {
+        let orientations = pattern.orientations();
+        self.keys().flat_map(move |pos| {
+            let matches = orientations
+                .iter()
+                .filter(|o| o.matches_at(self, pos))
+                .count();
+            iter::repeat_n(pos, matches)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn orientations(text: &str) -> Vec<(usize, usize)> {
+        let pattern = Pattern::parse(text, '.').unwrap();
+        (pattern.orientations().iter())
+            .map(|o| (o.rows(), o.columns()))
+            .collect()
+    }
+
+    #[test]
+    fn orientations_are_distinct() {
+        assert_eq!(orientations("A"), [(1, 1)]);
+        assert_eq!(orientations("XMAS"), [(1, 4), (1, 4), (4, 1), (4, 1)]);
+        assert_eq!(orientations("M.S\n.A.\nM.S").len(), 4);
+        assert_eq!(orientations("AB\nC.").len(), 8);
+        // the wildcard cells are part of the shape
+        assert_eq!(orientations("A.\n.A").len(), 2);
+    }
+
+    #[test]
+    fn finds_every_orientation() {
+        let grid = Grid::from("XMAS\nMM..\nA.A.\nS..S");
+        let xmas = Pattern::parse("XMAS", '.').unwrap();
+        assert_eq!(grid.find_pattern(&xmas).collect::<Vec<_>>(), [Pos2(0, 0)]);
+        let diagonal = Pattern::parse("X...\n.M..\n..A.\n...S", '.').unwrap();
+        assert_eq!(grid.find_pattern(&diagonal).count(), 1);
+
+        // a position is found once per orientation, a palindrome has just two
+        let grid = Grid::from("ABA\nB..\nA..");
+        let aba = Pattern::parse("ABA", '.').unwrap();
+        let found = grid.find_pattern_oriented(&aba).collect::<Vec<_>>();
+        assert_eq!(found, [Pos2(0, 0), Pos2(0, 0)]);
+    }
+}
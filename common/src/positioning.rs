@@ -1,6 +1,6 @@
 use std::{
     iter::Sum,
-    ops::{Add, AddAssign, Sub},
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
 };
 
 /// East
@@ -33,9 +33,204 @@ pub const DIRECTIONS_4: [Dir2; 4] = [E, S, W, N];
 /// Eight main directions with diagonals
 pub const DIRECTIONS_8: [Dir2; 8] = [NE, E, SE, S, SW, W, NW, N];
 
+/// A vector in 2D, for grids in `(row, column)` order.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Hash, PartialOrd, Ord)]
+pub struct Vec2<T>(pub T, pub T);
+
+/// A vector in 3D.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Hash, PartialOrd, Ord)]
+pub struct Vec3<T>(pub T, pub T, pub T);
+
+/// The coordinates of [`Vec2`] and [`Vec3`].
+pub trait Coordinate: Copy + Ord + Default + Add<Output = Self> + Mul<Output = Self> {
+    fn abs(self) -> Self;
+
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_coordinate {
+    (signed: $($s:ty)*; unsigned: $($u:ty)*) => {
+        $(impl Coordinate for $s {
+            fn abs(self) -> Self {
+                self.abs()
+            }
+
+            fn rem_euclid(self, rhs: Self) -> Self {
+                self.rem_euclid(rhs)
+            }
+        })*
+        $(impl Coordinate for $u {
+            fn abs(self) -> Self {
+                self
+            }
+
+            fn rem_euclid(self, rhs: Self) -> Self {
+                self.rem_euclid(rhs)
+            }
+        })*
+    };
+}
+
+impl_coordinate!(signed: i8 i16 i32 i64 i128 isize; unsigned: u8 u16 u32 u64 u128 usize);
+
+macro_rules! impl_vector {
+    ($name:ident($($f:tt),*)) => {
+        impl<T: Coordinate> $name<T> {
+            /// The sum of the absolute coordinates (taxicab distance to the
+            /// origin).
+            pub fn manhattan(self) -> T {
+                T::default() $(+ self.$f.abs())*
+            }
+
+            /// The largest absolute coordinate (king's move distance to the
+            /// origin).
+            pub fn chebyshev(self) -> T {
+                T::default() $(.max(self.$f.abs()))*
+            }
+
+            pub fn dot(self, rhs: Self) -> T {
+                T::default() $(+ self.$f * rhs.$f)*
+            }
+
+            /// Wraps every coordinate into `0..size` of the same coordinate.
+            pub fn rem_euclid(self, size: Self) -> Self {
+                Self($(self.$f.rem_euclid(size.$f)),*)
+            }
+        }
+
+        impl<T: Add<Output = T>> Add for $name<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self($(self.$f + rhs.$f),*)
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $name<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self($(self.$f - rhs.$f),*)
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $name<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                Self($(self.$f * rhs),*)
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $name<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self($(-self.$f),*)
+            }
+        }
+
+        impl<T: Copy + Add<Output = T>> AddAssign for $name<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T: Copy + Sub<Output = T>> SubAssign for $name<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<T: Default + Add<Output = T>> Sum for $name<T> {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::default(), |a, b| a + b)
+            }
+        }
+    };
+}
+
+impl_vector!(Vec2(0, 1));
+impl_vector!(Vec3(0, 1, 2));
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from(value: (T, T)) -> Self {
+        Self(value.0, value.1)
+    }
+}
+
+impl<T> From<Vec2<T>> for (T, T) {
+    fn from(value: Vec2<T>) -> Self {
+        (value.0, value.1)
+    }
+}
+
+impl<T> From<(T, T, T)> for Vec3<T> {
+    fn from(value: (T, T, T)) -> Self {
+        Self(value.0, value.1, value.2)
+    }
+}
+
+impl<T> From<Vec3<T>> for (T, T, T) {
+    fn from(value: Vec3<T>) -> Self {
+        (value.0, value.1, value.2)
+    }
+}
+
+/// The conversions of a newtype over [`Vec2`] from and to the vector and a
+/// tuple of its coordinates.
+macro_rules! impl_newtype {
+    ($name:ident($t:ty)) => {
+        impl $name {
+            /// The same coordinates as a [`Vec2`].
+            pub fn vec(self) -> Vec2<$t> {
+                self.into()
+            }
+        }
+
+        impl From<Vec2<$t>> for $name {
+            fn from(value: Vec2<$t>) -> Self {
+                Self(value.0, value.1)
+            }
+        }
+
+        impl From<$name> for Vec2<$t> {
+            fn from(value: $name) -> Self {
+                Self(value.0, value.1)
+            }
+        }
+
+        impl From<($t, $t)> for $name {
+            fn from(value: ($t, $t)) -> Self {
+                Self(value.0, value.1)
+            }
+        }
+
+        impl From<$name> for ($t, $t) {
+            fn from(value: $name) -> Self {
+                (value.0, value.1)
+            }
+        }
+    };
+}
+
+/// A position on a [`Grid`](crate::Grid).
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Hash, PartialOrd, Ord)]
 pub struct Pos2(pub usize, pub usize);
 
+/// A direction or an offset between positions.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Hash, PartialOrd, Ord)]
+pub struct Dir2(pub isize, pub isize);
+
+/// A position in an unbounded area, e.g. on a [`SparseGrid`](crate::SparseGrid).
+/// `pos + dir` of a [`Pos2`] and a [`Dir2`] is one as well.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Hash, PartialOrd, Ord)]
+pub struct IPos2(pub isize, pub isize);
+
+impl_newtype!(Pos2(usize));
+impl_newtype!(Dir2(isize));
+impl_newtype!(IPos2(isize));
+
 impl Pos2 {
     pub fn saturating_add_dir(self, dir: Dir2) -> Self {
         Self(
@@ -44,53 +239,79 @@ impl Pos2 {
         )
     }
 
+    /// The same position with signed coordinates.
+    pub fn signed(self) -> IPos2 {
+        IPos2(self.0 as isize, self.1 as isize)
+    }
+
+    /// The Manhattan distance between two positions.
     pub fn abs_diff(self, other: Self) -> usize {
         self.0.abs_diff(other.0) + self.1.abs_diff(other.1)
     }
 }
 
-impl From<(usize, usize)> for Pos2 {
-    fn from(value: (usize, usize)) -> Self {
-        Self(value.0, value.1)
+impl Dir2 {
+    /// The sum of the absolute coordinates (the number of orthogonal steps).
+    pub fn manhattan(self) -> isize {
+        self.vec().manhattan()
+    }
+
+    /// The largest absolute coordinate (the number of king's moves).
+    pub fn chebyshev(self) -> isize {
+        self.vec().chebyshev()
+    }
+
+    pub fn dot(self, rhs: Self) -> isize {
+        self.vec().dot(rhs.vec())
+    }
+
+    /// Rotate 90° counter clockwise
+    pub fn rotate_90_ccw(self) -> Self {
+        Self(-self.1, self.0)
+    }
+
+    /// Rotate 90° clockwise
+    pub fn rotate_90_cw(self) -> Self {
+        Self(self.1, -self.0)
     }
 }
 
-impl From<Pos2> for (usize, usize) {
-    fn from(value: Pos2) -> Self {
-        (value.0, value.1)
+impl IPos2 {
+    /// Wraps the position into an area of `size`, e.g. a torus-shaped
+    /// [`Grid`](crate::Grid). Panics if the area is empty.
+    pub fn rem_euclid(self, size: Pos2) -> Pos2 {
+        let Vec2(row, col) = self.vec().rem_euclid(size.signed().vec());
+        Pos2(row as usize, col as usize)
     }
 }
 
-impl TryFrom<(isize, isize)> for Pos2 {
+impl TryFrom<IPos2> for Pos2 {
     type Error = <usize as TryFrom<isize>>::Error;
 
-    fn try_from(value: (isize, isize)) -> Result<Self, Self::Error> {
+    fn try_from(value: IPos2) -> Result<Self, Self::Error> {
         Ok(Self(value.0.try_into()?, value.1.try_into()?))
     }
 }
 
-impl TryFrom<Dir2> for Pos2 {
-    type Error = <usize as TryFrom<isize>>::Error;
-
-    fn try_from(value: Dir2) -> Result<Self, Self::Error> {
-        Ok(Self(value.0.try_into()?, value.1.try_into()?))
+impl From<Pos2> for IPos2 {
+    fn from(value: Pos2) -> Self {
+        value.signed()
     }
 }
 
 impl Add for Pos2 {
     type Output = Self;
 
-    fn add(self, rhs: Pos2) -> Self::Output {
-        Self(self.0 + rhs.0, self.1 + rhs.1)
+    fn add(self, rhs: Self) -> Self::Output {
+        (self.vec() + rhs.vec()).into()
     }
 }
 
 impl Add<Dir2> for Pos2 {
-    type Output = Dir2;
+    type Output = IPos2;
 
     fn add(self, rhs: Dir2) -> Self::Output {
-        let lhs: Dir2 = self.into();
-        Dir2(lhs.0 + rhs.0, lhs.1 + rhs.1)
+        self.signed() + rhs
     }
 }
 
@@ -98,83 +319,141 @@ impl Sub for Pos2 {
     type Output = Dir2;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        let lhs: Dir2 = self.into();
-        let rhs = rhs.into();
-        lhs - rhs
+        self.signed() - rhs.signed()
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Hash, PartialOrd, Ord)]
-pub struct Dir2(pub isize, pub isize);
+impl Add for Dir2 {
+    type Output = Self;
 
-impl Dir2 {
-    /// Rotate 90° counter clockwise
-    pub fn rotate_90_ccw(self) -> Self {
-        Self(-self.1, self.0)
+    fn add(self, rhs: Self) -> Self::Output {
+        (self.vec() + rhs.vec()).into()
     }
+}
 
-    /// Rotate 90° clockwise
-    pub fn rotate_90_cw(self) -> Self {
-        Self(self.1, -self.0)
+impl Sub for Dir2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        (self.vec() - rhs.vec()).into()
     }
 }
 
-impl From<(isize, isize)> for Dir2 {
-    fn from(value: (isize, isize)) -> Self {
-        Self(value.0, value.1)
+impl Mul<isize> for Dir2 {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        (self.vec() * rhs).into()
     }
 }
 
-impl From<(usize, usize)> for Dir2 {
-    fn from(value: (usize, usize)) -> Self {
-        Self(value.0 as isize, value.1 as isize)
+impl Neg for Dir2 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        (-self.vec()).into()
     }
 }
 
-impl From<Dir2> for (isize, isize) {
-    fn from(value: Dir2) -> Self {
-        (value.0, value.1)
+impl AddAssign for Dir2 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
     }
 }
 
-impl TryFrom<Dir2> for (usize, usize) {
-    type Error = <usize as TryFrom<isize>>::Error;
-
-    fn try_from(value: Dir2) -> Result<Self, Self::Error> {
-        Ok((value.0.try_into()?, value.1.try_into()?))
+impl SubAssign for Dir2 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
     }
 }
 
-impl From<Pos2> for Dir2 {
-    fn from(value: Pos2) -> Self {
-        Dir2(value.0 as isize, value.1 as isize)
+impl Sum for Dir2 {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |a, b| a + b)
     }
 }
 
-impl Add for Dir2 {
+impl Add<Dir2> for IPos2 {
     type Output = Self;
 
     fn add(self, rhs: Dir2) -> Self::Output {
-        Self(self.0 + rhs.0, self.1 + rhs.1)
+        (self.vec() + rhs.vec()).into()
     }
 }
 
-impl Sub for Dir2 {
+impl Sub<Dir2> for IPos2 {
     type Output = Self;
 
+    fn sub(self, rhs: Dir2) -> Self::Output {
+        (self.vec() - rhs.vec()).into()
+    }
+}
+
+impl Sub for IPos2 {
+    type Output = Dir2;
+
     fn sub(self, rhs: Self) -> Self::Output {
-        Dir2(self.0 - rhs.0, self.1 - rhs.1)
+        (self.vec() - rhs.vec()).into()
     }
 }
 
-impl AddAssign for Dir2 {
-    fn add_assign(&mut self, rhs: Self) {
+impl AddAssign<Dir2> for IPos2 {
+    fn add_assign(&mut self, rhs: Dir2) {
         *self = *self + rhs;
     }
 }
 
-impl Sum for Dir2 {
-    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-        iter.fold(Dir2::default(), |a, b| a + b)
+impl SubAssign<Dir2> for IPos2 {
+    fn sub_assign(&mut self, rhs: Dir2) {
+        *self = *self - rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vectors() {
+        let v = Vec2(3, -4);
+        assert_eq!((v.manhattan(), v.chebyshev(), v.dot(Vec2(2, 1))), (7, 4, 2));
+        assert_eq!(
+            (v + Vec2(1, 1), v - Vec2(1, 1), v * 2, -v),
+            (Vec2(4, -3), Vec2(2, -5), Vec2(6, -8), Vec2(-3, 4))
+        );
+        assert_eq!([v, v, v].into_iter().sum::<Vec2<i32>>(), Vec2(9, -12));
+
+        let v = Vec3(1u8, 5, 2);
+        assert_eq!((v.manhattan(), v.chebyshev(), v.dot(v)), (8, 5, 30));
+        assert_eq!(Vec3(-1, 2, -3).manhattan(), 6);
+        assert_eq!(<(i8, i8, i8)>::from(Vec3(1, 2, 3)), (1, 2, 3));
+    }
+
+    #[test]
+    fn rem_euclid_of_negative_coordinates() {
+        assert_eq!(Vec2(-1, -7).rem_euclid(Vec2(5, 7)), Vec2(4, 0));
+        assert_eq!(Vec2(12, -13).rem_euclid(Vec2(5, 7)), Vec2(2, 1));
+        assert_eq!(IPos2(-1, -101).rem_euclid(Pos2(103, 101)), Pos2(102, 0));
+        assert_eq!(IPos2(-2, 5).rem_euclid(Pos2(3, 3)), Pos2(1, 2));
+    }
+
+    #[test]
+    fn positions_and_directions() {
+        let (a, b) = (Pos2(1, 4), Pos2(3, 2));
+        assert_eq!(a - b, Dir2(-2, 2));
+        assert_eq!(b + (a - b), a.signed());
+        assert_eq!(Pos2(0, 0) + N, IPos2(-1, 0));
+        assert_eq!(Pos2(0, 0).saturating_add_dir(NW), Pos2(0, 0));
+        assert_eq!(a.abs_diff(b), 4);
+        assert_eq!(Pos2::try_from(IPos2(1, 4)), Ok(a));
+        assert!(Pos2::try_from(Pos2(0, 0) + W).is_err());
+        assert_eq!(IPos2(5, 5) - IPos2(2, 7), Dir2(3, -2));
+
+        let dir = Dir2(1, -2);
+        assert_eq!((dir.manhattan(), dir.chebyshev(), dir.dot(E)), (3, 2, -2));
+        assert_eq!((-dir, dir * 3), (Dir2(-1, 2), Dir2(3, -6)));
+        assert_eq!(DIRECTIONS_8.into_iter().sum::<Dir2>(), Dir2(0, 0));
+        assert_eq!(E.rotate_90_cw(), S);
+        assert_eq!(E.rotate_90_ccw(), N);
     }
 }
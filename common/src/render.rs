@@ -0,0 +1,235 @@
+use crate::{Grid, Pos2};
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+};
+
+/// ANSI colours to highlight cells with, see [`Render::overlay`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    fn code(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+        }
+    }
+}
+
+/// How the cells of an overlay are highlighted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Highlight {
+    /// Replace the cell with this character.
+    Char(char),
+    /// Keep the cell, but paint it in this colour.
+    Color(Color),
+    /// Replace the cell with this character in this colour.
+    Both(char, Color),
+}
+
+impl From<char> for Highlight {
+    fn from(ch: char) -> Self {
+        Self::Char(ch)
+    }
+}
+
+impl From<Color> for Highlight {
+    fn from(color: Color) -> Self {
+        Self::Color(color)
+    }
+}
+
+/// A set of positions (e.g. a path or a region) that is highlighted when
+/// rendering a grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overlay {
+    pub positions: HashSet<Pos2>,
+    pub highlight: Highlight,
+}
+
+impl Overlay {
+    pub fn new(positions: impl IntoIterator<Item = Pos2>, highlight: impl Into<Highlight>) -> Self {
+        Self {
+            positions: positions.into_iter().collect(),
+            highlight: highlight.into(),
+        }
+    }
+}
+
+/// Renders a grid with a function that turns every cell into text, created
+/// with [`Grid::render`]. Use `to_string()` or `{}` to get the result.
+pub struct Render<'a, T, F> {
+    grid: &'a Grid<T>,
+    cell: F,
+    align: bool,
+    indices: bool,
+    overlays: Vec<Overlay>,
+}
+
+impl<T: Copy + PartialEq> Grid<T> {
+    /// Starts rendering this grid, `cell` turns every cell into text.
+    pub fn render<F, S>(&self, cell: F) -> Render<'_, T, F>
+    where
+        F: Fn(T) -> S,
+        S: Display,
+    {
+        Render {
+            grid: self,
+            cell,
+            align: false,
+            indices: false,
+            overlays: vec![],
+        }
+    }
+}
+
+impl<T, F, S> Render<'_, T, F>
+where
+    T: Copy + PartialEq,
+    F: Fn(T) -> S,
+    S: Display,
+{
+    /// Right-align all cells to the widest one and separate them with a space
+    /// (for numeric grids).
+    pub fn align(mut self) -> Self {
+        self.align = true;
+        self
+    }
+
+    /// Show row and column indices in the margin.
+    pub fn indices(mut self) -> Self {
+        self.indices = true;
+        self
+    }
+
+    /// Highlight the given positions, overlays that are added later take
+    /// precedence.
+    pub fn overlay(
+        mut self,
+        positions: impl IntoIterator<Item = Pos2>,
+        highlight: impl Into<Highlight>,
+    ) -> Self {
+        self.overlays.push(Overlay::new(positions, highlight));
+        self
+    }
+
+    /// Adds a number of prepared overlays, see [`Render::overlay`].
+    pub fn overlays(mut self, overlays: impl IntoIterator<Item = Overlay>) -> Self {
+        self.overlays.extend(overlays);
+        self
+    }
+}
+
+impl<T, F, S> Display for Render<'_, T, F>
+where
+    T: Copy + PartialEq,
+    F: Fn(T) -> S,
+    S: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let cells: Vec<Vec<String>> = self
+            .grid
+            .row_iter()
+            .map(|row| row.iter().map(|&c| (self.cell)(c).to_string()).collect())
+            .collect();
+        let width = if self.align {
+            let widest = cells.iter().flatten().map(|c| c.chars().count());
+            widest.max().unwrap_or_default()
+        } else {
+            1
+        };
+        let sep = if self.align { " " } else { "" };
+        let margin = (self.grid.rows().max(1) - 1).to_string().len();
+
+        if self.indices {
+            // Only the last digits of the column index that fit in a cell.
+            let modulo = 10_usize.saturating_pow(width as u32);
+            write!(f, "{:margin$} ", "")?;
+            for col in 0..self.grid.columns() {
+                let sep = if col == 0 { "" } else { sep };
+                write!(f, "{sep}{:>width$}", col % modulo)?;
+            }
+            writeln!(f)?;
+        }
+        for (row, cells) in cells.iter().enumerate() {
+            if self.indices {
+                write!(f, "{row:>margin$} ")?;
+            }
+            for (col, cell) in cells.iter().enumerate() {
+                if col > 0 {
+                    f.write_str(sep)?;
+                }
+                let pos = Pos2(row, col);
+                let highlight = self
+                    .overlays
+                    .iter()
+                    .rev()
+                    .find(|o| o.positions.contains(&pos))
+                    .map(|o| o.highlight);
+                match highlight {
+                    None => write!(f, "{cell:>width$}")?,
+                    Some(Highlight::Char(ch)) => write!(f, "{ch:>width$}")?,
+                    Some(Highlight::Color(color)) => {
+                        write!(f, "\x1b[{}m{cell:>width$}\x1b[0m", color.code())?
+                    }
+                    Some(Highlight::Both(ch, color)) => {
+                        write!(f, "\x1b[{}m{ch:>width$}\x1b[0m", color.code())?
+                    }
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders any grid with displayable cells, aligned in columns.
+impl<T: Copy + PartialEq + Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.render(|cell| cell).align().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aligns_cells_and_shows_indices() {
+        let grid: Grid<usize> = [[1, 20], [300, 4]].into_iter().collect();
+        assert_eq!(grid.to_string(), "  1  20\n300   4\n");
+        assert_eq!(
+            grid.render(|n| n).align().indices().to_string(),
+            "    0   1\n0   1  20\n1 300   4\n"
+        );
+
+        // only the last digit of a column index fits above a cell
+        let grid = Grid::from("abcdefghijk");
+        let rendered = grid.render(|c| c).indices().to_string();
+        assert_eq!(rendered, "  01234567890\n0 abcdefghijk\n");
+    }
+
+    #[test]
+    fn later_overlays_take_precedence() {
+        let grid = Grid::from("...\n...");
+        let rendered = grid
+            .render(|c| c)
+            .overlay([Pos2(0, 0), Pos2(0, 1)], 'O')
+            .overlays([Overlay::new([Pos2(0, 1)], Highlight::Both('X', Color::Red))])
+            .overlay([Pos2(1, 2)], Color::Green)
+            .to_string();
+        assert_eq!(rendered, "O\x1b[31mX\x1b[0m.\n..\x1b[32m.\x1b[0m\n");
+    }
+}
@@ -0,0 +1,163 @@
+use crate::{Case, Itertools};
+use serde_json::{Value, json};
+use std::{
+    fmt::Write,
+    iter::Sum,
+    ops::Add,
+    time::{Duration, Instant},
+};
+
+/// Wall time statistics of a number of runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timing {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timing {
+    /// Runs `f` the given number of times (at least once) and returns the
+    /// result of the last run together with the timing statistics.
+    pub fn measure<R>(runs: usize, mut f: impl FnMut() -> R) -> (R, Self) {
+        let mut times = Vec::with_capacity(runs);
+        let mut result = None;
+        for _ in 0..runs.max(1) {
+            let start = Instant::now();
+            result = Some(f());
+            times.push(start.elapsed());
+        }
+        times.sort_unstable();
+        let timing = Self {
+            min: times[0],
+            median: times[times.len() / 2],
+            max: times[times.len() - 1],
+        };
+        (result.unwrap(), timing)
+    }
+
+    fn to_json(self) -> Value {
+        json!({
+            "min_ns": self.min.as_nanos() as u64,
+            "median_ns": self.median.as_nanos() as u64,
+            "max_ns": self.max.as_nanos() as u64,
+        })
+    }
+}
+
+impl Add for Timing {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            min: self.min + rhs.min,
+            median: self.median + rhs.median,
+            max: self.max + rhs.max,
+        }
+    }
+}
+
+impl Sum for Timing {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |a, b| a + b)
+    }
+}
+
+/// The result of running a single case.
+#[derive(Debug, Clone)]
+pub struct Measurement {
+    pub day: &'static str,
+    pub case: Case,
+    pub answer: String,
+    pub timing: Timing,
+}
+
+/// Collects the measurements of a run of one or more days.
+#[derive(Debug, Default)]
+pub struct TimingReport {
+    pub runs: usize,
+    pub measurements: Vec<Measurement>,
+}
+
+impl TimingReport {
+    pub fn new(runs: usize) -> Self {
+        Self {
+            runs,
+            measurements: vec![],
+        }
+    }
+
+    /// Total timing per day, in the order the days were run.
+    pub fn per_day(&self) -> Vec<(&'static str, Timing)> {
+        self.measurements
+            .chunk_by(|a, b| a.day == b.day)
+            .map(|ms| (ms[0].day, ms.iter().map(|m| m.timing).sum()))
+            .collect()
+    }
+
+    pub fn total(&self) -> Timing {
+        self.measurements.iter().map(|m| m.timing).sum()
+    }
+
+    /// A table with min, median and max wall time of every case, followed by
+    /// a table summed per day when the report contains more than one day.
+    pub fn table(&self) -> String {
+        const HEADER: [&str; 3] = ["min", "median", "max"];
+        let label_width = self
+            .measurements
+            .iter()
+            .map(|m| m.day.len() + 2 + m.case.label().len())
+            .max()
+            .unwrap_or_default()
+            .max("total".len());
+        let mut out = String::new();
+        let mut row = |label: &str, cells: [String; 3]| {
+            let [min, median, max] = cells;
+            writeln!(
+                out,
+                "{label:label_width$}  {min:>10}  {median:>10}  {max:>10}"
+            )
+            .unwrap();
+        };
+        let fmt = |t: Timing| [t.min, t.median, t.max].map(|d| format!("{d:.2?}"));
+
+        row(&format!("{} runs", self.runs), HEADER.map(String::from));
+        for m in &self.measurements {
+            row(&format!("{}, {}", m.day, m.case.label()), fmt(m.timing));
+        }
+        let per_day = self.per_day();
+        if per_day.len() > 1 {
+            row("", Default::default());
+            row("day", HEADER.map(String::from));
+            for (day, timing) in per_day {
+                row(day, fmt(timing));
+            }
+        }
+        row("total", fmt(self.total()));
+        out
+    }
+
+    pub fn to_json(&self) -> Value {
+        let cases = self.measurements.iter().map(|m| {
+            json!({
+                "day": m.day,
+                "part": m.case.part,
+                "input": m.case.input,
+                "params": m.case.params,
+                "answer": m.answer,
+                "time": m.timing.to_json(),
+            })
+        });
+        let days = self.per_day().into_iter().map(|(day, timing)| {
+            json!({
+                "day": day,
+                "time": timing.to_json(),
+            })
+        });
+        json!({
+            "runs": self.runs,
+            "cases": cases.collect_vec(),
+            "days": days.collect_vec(),
+            "total": self.total().to_json(),
+        })
+    }
+}
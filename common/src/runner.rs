@@ -0,0 +1,546 @@
+use crate::{
+    Answers, DEFAULT_FPS, Itertools, Measurement, Result, SS, Timing, TimingReport, WrapErr, bail,
+    bench, ensure, eyre, set_animation_fps,
+};
+use color_eyre::Section;
+use std::{
+    any::Any,
+    collections::HashMap,
+    env,
+    fmt::Display,
+    fs, io,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+/// Environment variable that points to a directory with inputs, laid out as
+/// `<dir>/<day>/<input>.txt`. Useful to keep (private) inputs outside of the
+/// repository.
+pub const INPUTS_ENV: &str = "AOC_INPUTS";
+
+/// All cases of a single day, as generated by `boilerplate!`.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    /// Name of the day, e.g. `day17`.
+    pub name: &'static str,
+    /// Default directory to read the inputs from (the `src` directory of the
+    /// day).
+    pub dir: &'static str,
+    pub cases: &'static [Case],
+}
+
+impl Day {
+    /// The number of the day, e.g. `17` for `day17`.
+    pub fn nr(&self) -> Option<u8> {
+        self.name.strip_prefix("day")?.parse().ok()
+    }
+
+    /// Determine the directory to read inputs from, in order of preference:
+    /// - the given directory (e.g. from the `--inputs` flag)
+    /// - `$AOC_INPUTS/<day>`
+    /// - the `src` directory of the day
+    pub fn input_dir(&self, dir: Option<&Path>) -> PathBuf {
+        if let Some(dir) = dir {
+            return dir.to_owned();
+        }
+        match env::var_os(INPUTS_ENV) {
+            Some(root) => Path::new(&root).join(self.name),
+            None => self.dir.into(),
+        }
+    }
+
+    /// Finds the case with the given part, input and (stringified) params.
+    pub fn case(&self, part: &str, input: &str, params: &str) -> &'static Case {
+        self.cases
+            .iter()
+            .find(|c| c.part == part && c.input == input && c.params == params)
+            .unwrap_or_else(|| panic!("{} has no case {part}, {input}({params})", self.name))
+    }
+}
+
+/// One `(part, input)` combination from a `boilerplate!` invocation.
+///
+/// Every day exposes the complete list of its cases as `CASES`, which is what
+/// the generated `main` and the `aoc` runner iterate over.
+#[derive(Debug, Clone, Copy)]
+pub struct Case {
+    /// Name of the solver, e.g. `part1`.
+    pub part: &'static str,
+    /// Name of the input, e.g. `test` or `real`.
+    pub input: &'static str,
+    /// Extra parameters passed to the solver (stringified), may be empty.
+    pub params: &'static str,
+    /// The input text if it was given inline, otherwise it is read from
+    /// `<input>.txt`.
+    pub inline: Option<SS>,
+    /// Runs the solver (including the extra parameters) on any input.
+    pub solve: fn(SS) -> Result<String>,
+    pub attrs: Attrs,
+    /// The expected answer if it is given in `boilerplate!` (otherwise it may
+    /// be found in `answers.toml`).
+    pub expected: Option<fn() -> String>,
+}
+
+/// Attributes of a case, given as `#[ignore]`, `#[should_fail]` or
+/// `#[optional]` in front of the input in `boilerplate!`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Attrs {
+    /// Slow cases that only run when asked for (`--include-ignored`).
+    pub ignore: bool,
+    /// The solver is expected to panic or return an error on this input.
+    pub should_fail: bool,
+    /// The case is skipped when the input is missing (or empty), e.g. for
+    /// private inputs that cannot be committed.
+    pub optional: bool,
+}
+
+impl Attrs {
+    pub const NONE: Self = Self {
+        ignore: false,
+        should_fail: false,
+        optional: false,
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __attrs {
+    () => { $crate::Attrs::NONE };
+    (ignore $($rest:ident)*) => { $crate::Attrs { ignore: true, ..$crate::__attrs!($($rest)*) } };
+    (should_fail $($rest:ident)*) => { $crate::Attrs { should_fail: true, ..$crate::__attrs!($($rest)*) } };
+    (optional $($rest:ident)*) => { $crate::Attrs { optional: true, ..$crate::__attrs!($($rest)*) } };
+    ($other:ident $($rest:ident)*) => {
+        compile_error!(concat!(
+            "unknown case attribute `", stringify!($other), "`, expected `ignore`, `should_fail` or `optional`"
+        ))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __expected {
+    ($solve:expr;) => {
+        None
+    };
+    ($solve:expr; $value:expr) => {
+        Some(|| $crate::expected_answer($solve, $value))
+    };
+}
+
+/// Formats the expected value of a case, the solver is only used to infer the
+/// type of the value (e.g. of integer literals), just like `assert_eq!` would.
+#[doc(hidden)]
+pub fn expected_answer<A, V>(_solve: impl FnOnce(SS) -> A, value: V) -> String
+where
+    A: Answer,
+    A::Value: PartialEq<V>,
+    V: Display,
+{
+    value.to_string()
+}
+
+/// The assertion of a generated test, without an expected value the answer is
+/// checked against `answers.toml`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __check {
+    () => {
+        None
+    };
+    ($value:expr) => {
+        Some(|answer| assert_eq!(answer, $value))
+    };
+}
+
+/// The input of a generated test, embedded at compile time. `#[optional]`
+/// inputs are read at runtime instead (`None`), so that they can be missing.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __embedded {
+    ([] $input:ident) => { Some(include_str!(concat!(stringify!($input), ".txt"))) };
+    ([optional $($rest:ident)*] $input:ident) => { None };
+    ([$other:ident $($rest:ident)*] $input:ident) => { $crate::__embedded!([$($rest)*] $input) };
+}
+
+/// Adds `#[ignore]` to the test item if the attributes contain `ignore`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __test_attrs {
+    ([] $($item:tt)*) => { $($item)* };
+    ([ignore $($rest:ident)*] $($item:tt)*) => { $crate::__test_attrs!{ [$($rest)*] #[ignore] $($item)* } };
+    ([$other:ident $($rest:ident)*] $($item:tt)*) => { $crate::__test_attrs!{ [$($rest)*] $($item)* } };
+}
+
+impl Case {
+    /// A short label to identify this case, e.g. `part1, test(11, 7)`.
+    pub fn label(&self) -> String {
+        if self.params.is_empty() {
+            format!("{}, {}", self.part, self.input)
+        } else {
+            format!("{}, {}({})", self.part, self.input, self.params)
+        }
+    }
+
+    /// Runs the solver, for cases that should fail a panic is turned into an
+    /// error as well.
+    pub fn try_solve(&self, input: SS) -> Result<String> {
+        if !self.attrs.should_fail {
+            return (self.solve)(input);
+        }
+        self.solve_catching(input)
+    }
+
+    /// Runs the solver, a panic is turned into an error (without printing a
+    /// report).
+    pub fn solve_catching(&self, input: SS) -> Result<String> {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| ()));
+        let result = panic::catch_unwind(|| (self.solve)(input));
+        panic::set_hook(hook);
+        result.unwrap_or_else(|p| Err(panicked(p)))
+    }
+}
+
+fn panicked(payload: Box<dyn Any + Send>) -> color_eyre::Report {
+    let msg = (payload.downcast_ref::<&str>().copied())
+        .or(payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("<unknown>");
+    eyre!("panicked: {msg}")
+}
+
+/// The body of every test that is generated by `boilerplate!`, `check`
+/// asserts the answer, without it the answer is compared with the known
+/// answer in `answers.toml`. The input is `embedded` at compile time, only
+/// `#[optional]` inputs are read at runtime (and skipped when missing).
+pub fn run_test<A: Answer>(
+    day: &Day,
+    case: &Case,
+    embedded: Option<SS>,
+    solve: impl FnOnce(SS) -> A,
+    check: Option<fn(A::Value)>,
+) -> Result<()> {
+    let dir = day.input_dir(None);
+    let mut inputs = match embedded {
+        Some(text) => Inputs::fixed(text),
+        None => Inputs::new(&dir),
+    };
+    let Some(input) = inputs.get_if_present(case)? else {
+        eprintln!("skipped {}, {}: input not found", day.name, case.label());
+        return Ok(());
+    };
+    if case.attrs.should_fail {
+        match panic::catch_unwind(AssertUnwindSafe(|| solve(input).into_result())) {
+            Ok(Ok(answer)) => bail!("{} should fail, but returned {answer}", case.label()),
+            Ok(Err(error)) => eprintln!("{} failed as expected: {error}", case.label()),
+            Err(_) => eprintln!("{} panicked as expected", case.label()),
+        }
+        return Ok(());
+    }
+    let answer = solve(input).into_result()?;
+    match check {
+        Some(check) => check(answer),
+        None => {
+            let answers = Answers::load(&dir)?;
+            let expected = answers.get(case).ok_or_else(|| {
+                eyre!(
+                    "no answer for {} in {:?}, run {} with `--bless` to record it",
+                    case.label(),
+                    answers.path(),
+                    day.name
+                )
+            })?;
+            let answer = answer.to_string();
+            ensure!(
+                answer == expected,
+                "wrong answer for {}: expected {expected}, got {answer}",
+                case.label()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Everything a solver can return: an answer that can be displayed, or a
+/// [`Result`] of such an answer for solvers that can fail (e.g. on malformed
+/// input).
+pub trait Answer {
+    type Value: Display;
+
+    fn into_result(self) -> Result<Self::Value>;
+}
+
+impl<T: Display> Answer for Result<T> {
+    type Value = T;
+
+    fn into_result(self) -> Result<T> {
+        self
+    }
+}
+
+macro_rules! impl_answer {
+    ($($t:ty),*) => {$(
+        impl Answer for $t {
+            type Value = Self;
+
+            fn into_result(self) -> Result<Self> {
+                Ok(self)
+            }
+        }
+    )*};
+}
+
+impl_answer!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, String, &str
+);
+
+/// Reads inputs at runtime (instead of `include_str!`), every file is read
+/// only once.
+pub struct Inputs {
+    dir: PathBuf,
+    cache: HashMap<&'static str, SS>,
+    fixed: Option<SS>,
+}
+
+impl Inputs {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            cache: HashMap::new(),
+            fixed: None,
+        }
+    }
+
+    /// Use the given input for every case.
+    pub fn fixed(text: SS) -> Self {
+        Self {
+            fixed: Some(text),
+            ..Self::new("")
+        }
+    }
+
+    pub fn get(&mut self, case: &Case) -> Result<SS> {
+        self.get_if_present(&Case {
+            attrs: Attrs::NONE,
+            ..*case
+        })
+        .map(Option::unwrap)
+    }
+
+    /// Like [`Inputs::get`], but returns `None` for optional cases with a
+    /// missing (or empty) input.
+    pub fn get_if_present(&mut self, case: &Case) -> Result<Option<SS>> {
+        if let Some(text) = self.fixed.or(case.inline) {
+            return Ok(Some(text));
+        }
+        if let Some(&text) = self.cache.get(case.input) {
+            return Ok(Some(text));
+        }
+        let path = self.dir.join(format!("{}.txt", case.input));
+        let text = match fs::read_to_string(&path) {
+            Err(e) if case.attrs.optional && e.kind() == io::ErrorKind::NotFound => {
+                return Ok(None);
+            }
+            Ok(text) if case.attrs.optional && text.trim().is_empty() => return Ok(None),
+            text => text,
+        };
+        let text = text
+            .wrap_err_with(|| format!("could not read input {:?} from {path:?}", case.input))
+            .with_suggestion(|| {
+                format!("use `--inputs <dir>` or ${INPUTS_ENV} to read inputs from elsewhere")
+            })?;
+        // Solvers want `&'static str` inputs, and we only read every input
+        // once, so we can afford to leak them.
+        let text: SS = text.leak();
+        self.cache.insert(case.input, text);
+        Ok(Some(text))
+    }
+}
+
+/// Command line options that are shared by the generated `main` of every day
+/// and the `aoc` runner.
+#[derive(Debug, Default)]
+pub struct Options {
+    /// Directory to read the inputs from, see [`Day::input_dir`].
+    pub inputs: Option<PathBuf>,
+    /// Run every case this many times and report min, median and max time.
+    pub bench: Option<usize>,
+    /// Print the report as JSON instead of text.
+    pub json: bool,
+    /// Also run cases that are marked with `#[ignore]`.
+    pub include_ignored: bool,
+    /// Record the answers in `answers.toml`.
+    pub bless: bool,
+    /// Show animations of the simulations at this frame rate.
+    pub animate: Option<u32>,
+}
+
+impl Options {
+    pub const USAGE: &str =
+        "[--inputs <dir>] [--bench <runs>] [--json] [--include-ignored] [--bless]
+    [--animate] [--fps <n>]";
+
+    /// Tries to parse `arg` (and its value from `args`) as one of the shared
+    /// options, returns `false` if `arg` is not a shared option.
+    pub fn parse_arg(
+        &mut self,
+        arg: &str,
+        args: &mut impl Iterator<Item = String>,
+    ) -> Result<bool> {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| eyre!("missing value after `{arg}`"))
+        };
+        match arg {
+            "--inputs" => self.inputs = Some(value()?.into()),
+            "--bench" => {
+                let runs = value()?;
+                let runs = runs
+                    .parse()
+                    .ok()
+                    .filter(|&runs| runs > 0)
+                    .ok_or_else(|| eyre!("invalid number of runs: {runs:?}"))?;
+                self.bench = Some(runs);
+            }
+            "--json" => self.json = true,
+            "--include-ignored" => self.include_ignored = true,
+            "--bless" => self.bless = true,
+            "--animate" => self.animate = Some(self.animate.unwrap_or(DEFAULT_FPS)),
+            "--fps" => {
+                let fps = value()?;
+                let fps = fps
+                    .parse()
+                    .ok()
+                    .filter(|&fps| fps > 0)
+                    .ok_or_else(|| eyre!("invalid frame rate: {fps:?}"))?;
+                self.animate = Some(fps);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    pub fn inputs(&self, day: &Day) -> Inputs {
+        Inputs::new(day.input_dir(self.inputs.as_deref()))
+    }
+
+    /// Runs the given cases of a day and adds the measurements to the report,
+    /// answers are printed immediately unless we are benchmarking or
+    /// reporting JSON.
+    ///
+    /// Ignored cases and optional cases without input are skipped, cases that
+    /// should fail are checked but not measured. With `--bless` the answers
+    /// (of cases that are read from a file) are saved in `answers.toml`.
+    pub fn run(
+        &self,
+        day: &Day,
+        cases: &[&Case],
+        inputs: &mut Inputs,
+        report: &mut TimingReport,
+    ) -> Result<()> {
+        let quiet = self.bench.is_some() || self.json;
+        set_animation_fps(self.animate.unwrap_or_default());
+        let mut answers = self
+            .bless
+            .then(|| Answers::load(&day.input_dir(self.inputs.as_deref())))
+            .transpose()?;
+        for &case in cases {
+            let skip = |reason| {
+                if !quiet {
+                    println!("Skipped {}, {}: {reason}", day.name, case.label());
+                }
+            };
+            if case.attrs.ignore && !self.include_ignored {
+                skip("ignored");
+                continue;
+            }
+            let Some(input) = inputs.get_if_present(case)? else {
+                skip("input not found");
+                continue;
+            };
+            if case.attrs.should_fail {
+                match case.try_solve(input) {
+                    Ok(answer) => bail!(
+                        "{}, {} should fail, but returned {answer}",
+                        day.name,
+                        case.label()
+                    ),
+                    Err(error) if !quiet => println!(
+                        "Result of {}, {}: failed as expected ({error})",
+                        day.name,
+                        case.label()
+                    ),
+                    Err(_) => (),
+                }
+                continue;
+            }
+            let (answer, timing) = Timing::measure(report.runs, || (case.solve)(input));
+            let answer =
+                answer.wrap_err_with(|| format!("{}, {} failed", day.name, case.label()))?;
+            if !quiet {
+                println!(
+                    "Result of {}, {}: {answer} ({:.2?})",
+                    day.name,
+                    case.label(),
+                    timing.median
+                );
+            }
+            if let Some(answers) = answers.as_mut().filter(|_| case.inline.is_none()) {
+                answers.set(case, &answer);
+            }
+            report.measurements.push(Measurement {
+                day: day.name,
+                case: *case,
+                answer,
+                timing,
+            });
+        }
+        if let Some(answers) = answers {
+            answers.save()?;
+            if !quiet {
+                println!("Saved answers of {} to {:?}", day.name, answers.path());
+            }
+        }
+        Ok(())
+    }
+
+    /// Prints the final report (if any).
+    pub fn finish(&self, report: &TimingReport) {
+        if self.json {
+            println!("{:#}", report.to_json());
+        } else if self.bench.is_some() {
+            print!("{}", report.table());
+        }
+    }
+}
+
+/// The `main` of every day, runs all cases with inputs that are read at
+/// runtime.
+pub fn run_day(day: Day) -> Result<()> {
+    let mut options = Options::default();
+    let mut args = env::args().skip(1).peekable();
+    if args.next_if(|arg| arg == "bench").is_some() {
+        let config = bench::Config::from_args(args)?;
+        return bench::run(&[day], &config);
+    }
+    while let Some(arg) = args.next() {
+        if !options.parse_arg(&arg, &mut args)? {
+            bail!(
+                "unexpected argument: {arg:?}\n\n\
+                 Usage: {0} {1}\n       {0} bench [<filter>...] [options]",
+                day.name,
+                Options::USAGE
+            );
+        }
+    }
+
+    let mut report = TimingReport::new(options.bench.unwrap_or(1));
+    let mut inputs = options.inputs(&day);
+    options.run(
+        &day,
+        &day.cases.iter().collect_vec(),
+        &mut inputs,
+        &mut report,
+    )?;
+    options.finish(&report);
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use crate::{Grid, Heading, Neighbourhood, Pos2};
+use pathfinding::prelude::{astar, astar_bag};
+
+/// A shortest path search on a grid, created with [`Grid::search`]. Without a
+/// [`heuristic`](Self::heuristic) this is Dijkstra, with one it is A*.
+///
+/// The state of the search is the position and, with [`turns`](Self::turns),
+/// the heading. Searches that need more state (e.g. the number of steps in a
+/// straight line) can use [`pathfinding`](crate::pathfinding) directly.
+pub struct Search<'a, T, C, H = fn(Pos2) -> usize> {
+    grid: &'a Grid<T>,
+    cost: C,
+    heuristic: H,
+    neighbourhood: Neighbourhood,
+    turn_cost: Option<usize>,
+    heading: Option<Heading>,
+}
+
+/// The position and, when turns cost something, the heading.
+type State = (Pos2, Option<Heading>);
+
+impl<T: Copy + PartialEq> Grid<T> {
+    /// Starts a search where `cost(from, to)` is the cost of moving from one
+    /// cell to a neighbouring one, `None` if that move is not possible. E.g.
+    /// `grid.search(|_, (_, to)| (to != '#').then_some(1))`.
+    pub fn search<C>(&self, cost: C) -> Search<'_, T, C>
+    where
+        C: Fn((Pos2, T), (Pos2, T)) -> Option<usize>,
+    {
+        Search {
+            grid: self,
+            cost,
+            heuristic: |_| 0,
+            neighbourhood: Neighbourhood::Manhattan,
+            turn_cost: None,
+            heading: None,
+        }
+    }
+}
+
+impl<'a, T, C, H> Search<'a, T, C, H>
+where
+    T: Copy + PartialEq,
+    C: Fn((Pos2, T), (Pos2, T)) -> Option<usize>,
+    H: Fn(Pos2) -> usize,
+{
+    /// The directions to move in, [`Neighbourhood::Manhattan`] by default.
+    pub fn neighbourhood(mut self, neighbourhood: Neighbourhood) -> Self {
+        self.neighbourhood = neighbourhood;
+        self
+    }
+
+    /// Start with `heading` and add `cost` for every 90° turn, so turning
+    /// around costs twice as much. A 45° turn (with [`Neighbourhood::All`])
+    /// costs half of it, rounded down for an odd `cost`.
+    pub fn turns(mut self, heading: Heading, cost: usize) -> Self {
+        self.heading = Some(heading);
+        self.turn_cost = Some(cost);
+        self
+    }
+
+    /// An estimate of the remaining cost from a position to the goal, it
+    /// should never be too high (e.g. the Manhattan distance).
+    pub fn heuristic<H2: Fn(Pos2) -> usize>(self, heuristic: H2) -> Search<'a, T, C, H2> {
+        Search {
+            grid: self.grid,
+            cost: self.cost,
+            heuristic,
+            neighbourhood: self.neighbourhood,
+            turn_cost: self.turn_cost,
+            heading: self.heading,
+        }
+    }
+
+    /// The cheapest path from `start` (included) to a position for which
+    /// `goal` holds, and its cost.
+    pub fn best(&self, start: Pos2, goal: impl Fn(Pos2) -> bool) -> Option<(Vec<Pos2>, usize)> {
+        let (path, cost) = astar(
+            &(start, self.heading),
+            |state| self.successors(state),
+            |&(pos, _)| (self.heuristic)(pos),
+            |&(pos, _)| goal(pos),
+        )?;
+        Some((path.into_iter().map(|(pos, _)| pos).collect(), cost))
+    }
+
+    /// All paths that are as cheap as the [`best`](Self::best) one, and their
+    /// cost.
+    pub fn all_best(
+        &self,
+        start: Pos2,
+        goal: impl Fn(Pos2) -> bool,
+    ) -> Option<(impl Iterator<Item = Vec<Pos2>>, usize)> {
+        let (paths, cost) = astar_bag(
+            &(start, self.heading),
+            |state| self.successors(state),
+            |&(pos, _)| (self.heuristic)(pos),
+            |&(pos, _)| goal(pos),
+        )?;
+        let paths = paths.map(|path| path.into_iter().map(|(pos, _)| pos).collect());
+        Some((paths, cost))
+    }
+
+    fn successors(&self, &(pos, heading): &State) -> Vec<(State, usize)> {
+        self.neighbourhood
+            .headings()
+            .iter()
+            .filter_map(|&to| {
+                let (next, cell) = self.grid.step(pos, to.dir())?;
+                let mut cost = (self.cost)((pos, self.grid[pos]), (next, cell))?;
+                if let (Some(heading), Some(turn_cost)) = (heading, self.turn_cost) {
+                    cost += turn_cost * heading.turns_to(to) / 2;
+                }
+                Some(((next, heading.map(|_| to)), cost))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open(_: (Pos2, char), (_, to): (Pos2, char)) -> Option<usize> {
+        (to != '#').then_some(1)
+    }
+
+    #[test]
+    fn finds_all_cheapest_paths() {
+        let grid = Grid::from("...\n.#.\n...");
+        let (start, end) = (Pos2(0, 0), Pos2(2, 2));
+        let search = grid.search(open).heuristic(|pos| pos.abs_diff(end));
+        let (path, cost) = search.best(start, |pos| pos == end).unwrap();
+        assert_eq!((path.len(), cost), (5, 4));
+        let (paths, cost) = search.all_best(start, |pos| pos == end).unwrap();
+        assert_eq!((paths.count(), cost), (2, 4));
+
+        let search = grid.search(open).neighbourhood(Neighbourhood::All);
+        assert!(search.best(start, |pos| pos == Pos2(1, 1)).is_none());
+        assert_eq!(search.best(start, |pos| pos == end).unwrap().1, 3);
+    }
+
+    #[test]
+    fn turns_cost_extra() {
+        let grid = Grid::from("...\n...\n...");
+        let (start, end) = (Pos2(0, 0), Pos2(2, 2));
+        let search = grid.search(open).turns(Heading::E, 1000);
+        let (paths, cost) = search.all_best(start, |pos| pos == end).unwrap();
+        let paths = paths.collect::<Vec<_>>();
+        assert_eq!(cost, 1004);
+        assert_eq!(paths, [[start, Pos2(0, 1), Pos2(0, 2), Pos2(1, 2), end]]);
+
+        // 45° turns cost half, rounded down
+        let search = grid
+            .search(open)
+            .neighbourhood(Neighbourhood::All)
+            .turns(Heading::E, 3);
+        let (path, cost) = search.best(start, |pos| pos == end).unwrap();
+        assert_eq!((path, cost), (vec![start, Pos2(1, 1), end], 3));
+    }
+}
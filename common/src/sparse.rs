@@ -0,0 +1,269 @@
+use crate::{Dir2, Grid, GridCell, GridLike, IPos2, Itertools, Neighbourhood, Pos2};
+use pathfinding::prelude::bfs_reach;
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    fmt::{self, Debug, Formatter, Write},
+    iter,
+    ops::Index,
+};
+
+/// A grid that only stores the cells that are set, for unbounded areas. Cells
+/// are keyed by signed positions ([`IPos2`]) in the same `(row, column)` order
+/// as on a [`Grid`].
+///
+/// An infinite grid ([`SparseGrid::with_default`]) has a cell everywhere, the
+/// ones that were never set have the default value. [`GridLike::walk`] and
+/// [`GridLike::bfs_reachable`] would never end on it, so they stay within the
+/// bounding box of the cells that are set, grown by one cell on every side (so
+/// a flood fill can still go around the outside).
+#[derive(Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<IPos2, T>,
+    default: Option<T>,
+}
+
+impl<T: Copy + PartialEq> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy + PartialEq> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::new(),
+            default: None,
+        }
+    }
+
+    /// An infinite grid, every cell that is not set has the value `default`.
+    pub fn with_default(default: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            default: Some(default),
+        }
+    }
+
+    pub fn get(&self, pos: impl Into<IPos2>) -> Option<T> {
+        self.cells.get(&pos.into()).copied().or(self.default)
+    }
+
+    /// Sets the cell at `pos`, returns the previous value if it was set.
+    pub fn insert(&mut self, pos: impl Into<IPos2>, cell: T) -> Option<T> {
+        self.cells.insert(pos.into(), cell)
+    }
+
+    /// Clears the cell at `pos` (back to the default of an infinite grid).
+    pub fn remove(&mut self, pos: impl Into<IPos2>) -> Option<T> {
+        self.cells.remove(&pos.into())
+    }
+
+    /// E.g. `*grid.entry(pos).or_insert(0) += 1` to count robots.
+    pub fn entry(&mut self, pos: impl Into<IPos2>) -> Entry<'_, IPos2, T> {
+        self.cells.entry(pos.into())
+    }
+
+    /// The number of cells that are set.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// A dense grid of the bounding box of all cells that are set, other cells
+    /// are `background`. Also returns the position of the top left corner, so
+    /// `Pos2(0, 0)` of the dense grid is at that position in this grid. This is
+    /// how a sparse grid is rendered, e.g.
+    /// `sparse.to_grid('.').0.render(|c| c)`.
+    pub fn to_grid(&self, background: T) -> (Grid<T>, IPos2) {
+        let Some((min, max)) = GridLike::bounds(self) else {
+            return (Grid::new(0, 0, background), IPos2::default());
+        };
+        let size = max - min;
+        let mut grid = Grid::new(size.0 as usize + 1, size.1 as usize + 1, background);
+        for (&pos, &cell) in &self.cells {
+            let Dir2(row, col) = pos - min;
+            grid[Pos2(row as usize, col as usize)] = cell;
+        }
+        (grid, min)
+    }
+
+    /// Whether `walk` and `bfs_reachable` may visit `pos`, see [`SparseGrid`].
+    fn explorable(&self) -> impl Fn(IPos2) -> bool {
+        let bounds = self
+            .default
+            .is_some()
+            .then(|| GridLike::bounds(self).map(|(min, max)| (min - Dir2(1, 1), max + Dir2(1, 1))));
+        move |pos| match bounds {
+            None => true,
+            Some(bounds) => bounds.is_some_and(|(min, max)| {
+                (min.0..=max.0).contains(&pos.0) && (min.1..=max.1).contains(&pos.1)
+            }),
+        }
+    }
+}
+
+impl<T: Copy + PartialEq> GridLike for SparseGrid<T> {
+    type Pos = IPos2;
+    type Cell = T;
+
+    fn get(&self, pos: IPos2) -> Option<T> {
+        SparseGrid::get(self, pos)
+    }
+
+    fn offset(&self, pos: IPos2, dir: Dir2) -> Option<IPos2> {
+        Some(IPos2(pos.0.checked_add(dir.0)?, pos.1.checked_add(dir.1)?))
+    }
+
+    fn items(&self) -> impl Iterator<Item = (IPos2, T)> {
+        self.cells.iter().map(|(&pos, &cell)| (pos, cell))
+    }
+
+    fn bounds(&self) -> Option<(IPos2, IPos2)> {
+        let mut keys = self.cells.keys().copied();
+        let first = keys.next()?;
+        Some(keys.fold((first, first), |(min, max), pos| {
+            (
+                IPos2(min.0.min(pos.0), min.1.min(pos.1)),
+                IPos2(max.0.max(pos.0), max.1.max(pos.1)),
+            )
+        }))
+    }
+
+    fn walk(&self, start: IPos2, dir: Dir2) -> impl Iterator<Item = (IPos2, T)> {
+        let explorable = self.explorable();
+        let first = (self.get(start))
+            .filter(|_| explorable(start))
+            .map(|cell| (start, cell));
+        iter::successors(first, move |&(pos, _)| {
+            self.step(pos, dir).filter(|&(next, _)| explorable(next))
+        })
+    }
+
+    fn bfs_reachable(
+        &self,
+        start: IPos2,
+        neighbourhood: Neighbourhood,
+        mut predicate: impl FnMut(IPos2, T) -> bool,
+    ) -> impl Iterator<Item = IPos2> {
+        let explorable = self.explorable();
+        let reachable = bfs_reach(start, |&pos| {
+            self.neighbours(pos, neighbourhood)
+                .filter(|&(pos, cell)| explorable(pos) && predicate(pos, cell))
+                .map(|(pos, _)| pos)
+                .collect_vec()
+        });
+        reachable.collect_vec().into_iter()
+    }
+}
+
+impl<T: Copy + PartialEq> FromIterator<(IPos2, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (IPos2, T)>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+            default: None,
+        }
+    }
+}
+
+impl<T: Copy + PartialEq> From<&Grid<T>> for SparseGrid<T> {
+    fn from(grid: &Grid<T>) -> Self {
+        grid.items().map(|(pos, cell)| (pos.into(), cell)).collect()
+    }
+}
+
+impl<T> Index<IPos2> for SparseGrid<T> {
+    type Output = T;
+
+    /// Panics if the cell is not set (and there is no default).
+    fn index(&self, pos: IPos2) -> &T {
+        match (self.cells.get(&pos), &self.default) {
+            (Some(cell), _) | (None, Some(cell)) => cell,
+            (None, None) => panic!("no cell at {pos:?}"),
+        }
+    }
+}
+
+/// Prints the bounding box of the cells that are set, other cells are blank.
+impl<T: GridCell + PartialEq> Debug for SparseGrid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Some((min, max)) = GridLike::bounds(self) else {
+            return Ok(());
+        };
+        for row in min.0..=max.0 {
+            for col in min.1..=max.1 {
+                let cell = self.cells.get(&IPos2(row, col));
+                f.write_char(cell.map_or(' ', |c| c.to_char()))?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{E, S};
+
+    fn at(row: isize, col: isize) -> IPos2 {
+        IPos2(row, col)
+    }
+
+    #[test]
+    fn infinite_grid_is_explored_around_its_cells() {
+        let mut grid = SparseGrid::with_default('.');
+        grid.insert(at(0, 0), '#');
+        grid.insert(at(2, 3), '#');
+
+        let walked = grid.walk(at(1, 0), E).map(|(pos, _)| pos).collect_vec();
+        assert_eq!(walked, (0..=4).map(|col| at(1, col)).collect_vec());
+        assert_eq!(grid.walk(at(10, 10), S).count(), 0);
+
+        // everything in the bounding box grown by one, except the walls
+        let reachable = grid.bfs_reachable(at(-1, -1), Neighbourhood::Manhattan, |_, c| c == '.');
+        assert_eq!(reachable.count(), 5 * 6 - 2);
+    }
+
+    #[test]
+    fn cells_can_be_set_and_cleared() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.insert(at(-5, 3), 1), None);
+        assert_eq!(grid.insert(at(-5, 3), 2), Some(1));
+        *grid.entry(at(7, -1)).or_insert(0) += 1;
+        assert_eq!(
+            (grid.len(), grid[at(-5, 3)], grid.get(at(0, 0))),
+            (2, 2, None)
+        );
+        assert_eq!(grid.remove(at(-5, 3)), Some(2));
+        assert_eq!(grid.get(at(-5, 3)), None);
+
+        let mut grid = SparseGrid::with_default(0);
+        grid.insert(at(1, 1), 5);
+        assert_eq!((grid[at(1, 1)], grid[at(-100, 100)], grid.len()), (5, 0, 1));
+        grid.remove(at(1, 1));
+        assert!(grid.is_empty());
+        assert_eq!(grid.get(at(1, 1)), Some(0));
+    }
+
+    #[test]
+    fn converts_from_a_dense_grid() {
+        let sparse = SparseGrid::from(&Grid::from("ab\ncd"));
+        assert_eq!(sparse.get(Pos2(1, 0)), Some('c'));
+        assert_eq!(GridLike::bounds(&sparse), Some((at(0, 0), at(1, 1))));
+        assert_eq!(format!("{sparse:?}"), "ab\ncd\n");
+    }
+
+    #[test]
+    fn finite_grid_ends_at_unset_cells() {
+        let grid: SparseGrid<char> = [(at(0, 0), '.'), (at(0, 1), '.'), (at(0, 3), '.')]
+            .into_iter()
+            .collect();
+        assert_eq!(grid.walk(at(0, 0), E).count(), 2);
+        let (dense, origin) = grid.to_grid(' ');
+        assert_eq!(origin, at(0, 0));
+        assert_eq!(format!("{dense:?}"), ".. .\n");
+    }
+}
@@ -0,0 +1,112 @@
+//! Geometric transformations of a [`Grid`], they all return a new grid.
+
+use crate::{Grid, Pos2};
+use pathfinding::matrix::Matrix;
+
+impl<T: Copy + PartialEq> Grid<T> {
+    /// Rotates 90° clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        Self(self.0.rotated_cw(1))
+    }
+
+    /// Rotates 90° counter clockwise.
+    pub fn rotate_ccw(&self) -> Self {
+        Self(self.0.rotated_ccw(1))
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        Self(self.0.rotated_cw(2))
+    }
+
+    /// Mirrors along the diagonal from the top left, rows become columns.
+    pub fn transpose(&self) -> Self {
+        Self(self.0.transposed())
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        Self(self.0.flipped_lr())
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        Self(self.0.flipped_ud())
+    }
+
+    /// The part of the grid from `min` to `max` (inclusive, like
+    /// [`GridLike::bounds`](crate::GridLike::bounds)).
+    ///
+    /// Panics if the rectangle is not inside the grid.
+    pub fn crop(&self, min: Pos2, max: Pos2) -> Self {
+        match self.0.slice(min.0..max.0 + 1, min.1..max.1 + 1) {
+            Ok(slice) => Self(slice),
+            Err(_) => panic!(
+                "cannot crop {min:?}..={max:?} from a grid of {}x{}",
+                self.rows(),
+                self.columns()
+            ),
+        }
+    }
+
+    /// Surrounds the grid with a border of `width` cells of `border`.
+    pub fn pad(&self, width: usize, border: T) -> Self {
+        let mut padded = Matrix::new(self.rows() + 2 * width, self.columns() + 2 * width, border);
+        padded.set_slice((width, width), &self.0);
+        Self(padded)
+    }
+
+    /// Repeats the grid `rows` times downwards and `columns` times to the
+    /// right.
+    pub fn tile(&self, rows: usize, columns: usize) -> Self {
+        let (height, width) = (self.rows(), self.columns());
+        Self(Matrix::from_fn(height * rows, width * columns, |(r, c)| {
+            self.0[(r % height, c % width)]
+        }))
+    }
+
+    /// Replaces every cell with `N` cells next to each other, e.g. to make a
+    /// grid twice as wide:
+    /// `grid.scale_columns(|c| match c { 'O' => ['[', ']'], c => [c, c] })`.
+    pub fn scale_columns<R, const N: usize>(&self, f: impl FnMut(T) -> [R; N]) -> Grid<R> {
+        let cells = self.0.values().copied().flat_map(f).collect();
+        Grid(Matrix::from_vec(self.rows(), self.columns() * N, cells).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(grid: &Grid<char>) -> String {
+        format!("{grid:?}")
+    }
+
+    #[test]
+    fn rotations_and_reflections() {
+        let grid = Grid::from("abc\ndef");
+        assert_eq!(text(&grid.rotate_cw()), "da\neb\nfc\n");
+        assert_eq!(text(&grid.rotate_ccw()), "cf\nbe\nad\n");
+        assert_eq!(text(&grid.rotate_180()), "fed\ncba\n");
+        assert_eq!(text(&grid.transpose()), "ad\nbe\ncf\n");
+        assert_eq!(text(&grid.flip_horizontal()), "cba\nfed\n");
+        assert_eq!(text(&grid.flip_vertical()), "def\nabc\n");
+        assert!(grid.rotate_cw().rotate_ccw() == grid);
+        assert!(grid.transpose() == grid.rotate_cw().flip_horizontal());
+    }
+
+    #[test]
+    fn resizing() {
+        let grid = Grid::from("abc\ndef");
+        assert_eq!(text(&grid.crop(Pos2(0, 1), Pos2(1, 2))), "bc\nef\n");
+        assert_eq!(text(&grid.crop(Pos2(1, 0), Pos2(1, 0))), "d\n");
+        assert_eq!(text(&grid.pad(1, '.')), ".....\n.abc.\n.def.\n.....\n");
+        assert_eq!(text(&grid.tile(2, 2)), "abcabc\ndefdef\nabcabc\ndefdef\n");
+        assert_eq!(text(&grid.scale_columns(|c| [c, '.'])), "a.b.c.\nd.e.f.\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot crop")]
+    fn crop_outside_the_grid() {
+        Grid::from("ab").crop(Pos2(0, 1), Pos2(0, 2));
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{Dir2, Grid, GridLike, IPos2, Neighbourhood, Pos2};
+use std::{
+    iter,
+    ops::{Deref, DerefMut, Index, IndexMut},
+};
+
+/// A view of a [`Grid`] that wraps around at the edges (a torus), created with
+/// [`Grid::wrapping`] or [`Grid::wrapping_mut`]. Stepping off one edge enters
+/// the grid at the opposite edge, and a signed position (an [`IPos2`], e.g.
+/// `pos + dir`) is wrapped into the grid when indexing.
+pub struct Wrapping<G> {
+    grid: G,
+}
+
+impl<T: Copy + PartialEq> Grid<T> {
+    pub fn wrapping(&self) -> Wrapping<&Self> {
+        Wrapping { grid: self }
+    }
+
+    pub fn wrapping_mut(&mut self) -> Wrapping<&mut Self> {
+        Wrapping { grid: self }
+    }
+}
+
+impl<T, G> Wrapping<G>
+where
+    T: Copy + PartialEq,
+    G: Deref<Target = Grid<T>>,
+{
+    /// The position in the grid of a signed position. Panics if the grid is
+    /// empty, there is no position to wrap to.
+    pub fn wrap(&self, pos: impl Into<IPos2>) -> Pos2 {
+        pos.into()
+            .rem_euclid(Pos2(self.grid.rows(), self.grid.columns()))
+    }
+
+    /// Unlike [`Grid::step`], there is always a next cell.
+    pub fn step(&self, start: Pos2, dir: Dir2) -> (Pos2, T) {
+        let pos = self.wrap(start + dir);
+        (pos, self.grid[pos])
+    }
+
+    /// Walks from `start` (included) in `dir`, forever.
+    pub fn walk<'a>(
+        &'a self,
+        start: Pos2,
+        dir: Dir2,
+    ) -> impl Iterator<Item = (Pos2, T)> + use<'a, T, G>
+    where
+        T: 'a,
+    {
+        iter::successors(Some((start, self.grid[start])), move |&(pos, _)| {
+            Some(self.step(pos, dir))
+        })
+    }
+
+    pub fn neighbours<'a>(
+        &'a self,
+        pos: Pos2,
+        neighbourhood: Neighbourhood,
+    ) -> impl Iterator<Item = (Pos2, T)> + use<'a, T, G>
+    where
+        T: 'a,
+    {
+        neighbourhood
+            .directions()
+            .iter()
+            .map(move |&dir| self.step(pos, dir))
+    }
+}
+
+macro_rules! impl_grid_like {
+    ($($grid:ty),*) => {$(
+        impl<'a, T: Copy + PartialEq> GridLike for Wrapping<$grid> {
+            type Pos = Pos2;
+            type Cell = T;
+
+            fn get(&self, pos: Pos2) -> Option<T> {
+                self.grid.get(pos)
+            }
+
+            fn offset(&self, pos: Pos2, dir: Dir2) -> Option<Pos2> {
+                Some(self.wrap(pos + dir))
+            }
+
+            fn items(&self) -> impl Iterator<Item = (Pos2, T)> {
+                self.grid.items()
+            }
+
+            fn bounds(&self) -> Option<(Pos2, Pos2)> {
+                GridLike::bounds(&*self.grid)
+            }
+        }
+    )*};
+}
+
+impl_grid_like!(&'a Grid<T>, &'a mut Grid<T>);
+
+impl<T, G> Index<IPos2> for Wrapping<G>
+where
+    T: Copy + PartialEq,
+    G: Deref<Target = Grid<T>>,
+{
+    type Output = T;
+
+    fn index(&self, pos: IPos2) -> &T {
+        &self.grid[self.wrap(pos)]
+    }
+}
+
+impl<T, G> IndexMut<IPos2> for Wrapping<G>
+where
+    T: Copy + PartialEq,
+    G: DerefMut<Target = Grid<T>>,
+{
+    fn index_mut(&mut self, pos: IPos2) -> &mut T {
+        let pos = self.wrap(pos);
+        &mut self.grid[pos]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{E, N, NW};
+
+    #[test]
+    fn wraps_around_the_edges() {
+        let grid = Grid::from("abc\ndef");
+        let wrapping = grid.wrapping();
+        assert_eq!(wrapping.wrap(IPos2(-1, -1)), Pos2(1, 2));
+        assert_eq!(wrapping.wrap(IPos2(5, 7)), Pos2(1, 1));
+        assert_eq!(wrapping.step(Pos2(0, 0), NW), (Pos2(1, 2), 'f'));
+        assert_eq!(wrapping[Pos2(0, 0) + N], 'd');
+
+        let walked = wrapping.walk(Pos2(1, 1), E).map(|(_, c)| c);
+        assert_eq!(walked.take(5).collect::<String>(), "efdef");
+        let neighbours = wrapping.neighbours(Pos2(0, 0), Neighbourhood::Manhattan);
+        assert_eq!(neighbours.map(|(_, c)| c).collect::<String>(), "bdcd");
+    }
+
+    #[test]
+    fn updates_through_wrapped_positions() {
+        let mut grid = Grid::new(2, 3, 0);
+        let mut wrapping = grid.wrapping_mut();
+        for col in -3..3 {
+            wrapping[IPos2(-1, col)] += 1;
+        }
+        assert_eq!(grid.row_iter().collect::<Vec<_>>(), [[0, 0, 0], [2, 2, 2]]);
+    }
+}
@@ -36,7 +36,7 @@ fn simulate_stone<const ROUNDS: usize>(
         simulate_stone(memo, 1, round + 1)
     } else {
         let digits = mark.ilog10() + 1;
-        if digits % 2 == 0 {
+        if digits.is_multiple_of(2) {
             let split = MARK_10.pow(digits / 2);
             simulate_stone(memo, mark / split, round + 1)
                 + simulate_stone(memo, mark % split, round + 1)
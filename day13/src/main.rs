@@ -7,46 +7,36 @@ struct Machine {
     prize: (usize, usize),
 }
 
-fn part1(input: SS) -> usize {
-    parse(input).filter_map(eval_machine).sum()
+fn part1(input: SS) -> Result<usize> {
+    Ok(parse(input)?.into_iter().filter_map(eval_machine).sum())
 }
 
-fn part2(input: SS) -> usize {
+fn part2(input: SS) -> Result<usize> {
     const OFFSET: usize = 10_000_000_000_000;
 
-    parse(input)
+    Ok(parse(input)?
+        .into_iter()
         .update(|m| {
             m.prize.0 += OFFSET;
             m.prize.1 += OFFSET;
         })
         .filter_map(eval_machine)
-        .sum()
+        .sum())
 }
 
-fn parse(input: SS) -> impl Iterator<Item = Machine> {
-    input
-        .split("\n\n")
-        .map(|s| {
-            let mut lines = s.lines();
-            let (a0, a1) = lines
-                .next()?
-                .strip_prefix("Button A: X+")?
-                .split_once(", Y+")?;
-            let (b0, b1) = lines
-                .next()?
-                .strip_prefix("Button B: X+")?
-                .split_once(", Y+")?;
-            let (p0, p1) = lines
-                .next()?
-                .strip_prefix("Prize: X=")?
-                .split_once(", Y=")?;
-            Some(Machine {
-                a: (to_usize(a0), to_usize(a1)),
-                b: (to_usize(b0), to_usize(b1)),
-                prize: (to_usize(p0), to_usize(p1)),
+fn parse(input: SS) -> Result<Vec<Machine>> {
+    const TEMPLATE: &str = "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}";
+
+    sections(input)
+        .map(|section| {
+            let (a0, a1, b0, b1, p0, p1) = scan(TEMPLATE, &section)?;
+            Ok(Machine {
+                a: (a0, a1),
+                b: (b0, b1),
+                prize: (p0, p1),
             })
         })
-        .map(Option::unwrap)
+        .collect()
 }
 
 // a_presses * a0 + b_presses * b0 = p0
@@ -2,76 +2,85 @@ use common::*;
 
 #[derive(Clone, Copy)]
 struct Robot {
-    pos: (isize, isize),
-    dir: (isize, isize),
+    pos: Pos2,
+    dir: Dir2,
 }
 
 impl Robot {
-    fn go(&self, secs: isize, width: isize, height: isize) -> Self {
-        Self {
-            pos: (
-                (self.pos.0 + self.dir.0 * secs).rem_euclid(width),
-                (self.pos.1 + self.dir.1 * secs).rem_euclid(height),
-            ),
-            ..*self
-        }
+    /// Where the robot would be after `secs` in an endless area, the tiles wrap
+    /// it around.
+    fn go(&self, secs: isize) -> IPos2 {
+        self.pos + self.dir * secs
     }
 }
 
-fn part1(input: SS, width: isize, height: isize) -> usize {
-    let robots = parse(input).map(|r| r.go(100, width, height));
-    calc_chaos(robots, width, height)
+fn part1(input: SS, width: usize, height: usize) -> Result<usize> {
+    let robots = parse(input)?;
+    Ok(calc_chaos(Pos2(height, width), &robots, 100))
 }
 
-fn part2(input: SS, width: isize, height: isize) -> isize {
-    // I have observed the tree by looking at an animation of `dump_grid` to be
+fn part2(input: SS, width: usize, height: usize) -> Result<isize> {
+    // I have observed the tree by looking at an animation (`--animate`) to be
     // clustered in one of the quadrants, so we should be able to use the method
     // of part1 to observe an unusual low "safety factor" when the tree appears.
-    let robots = parse(input).collect_vec();
+    let robots = parse(input)?;
+    let size = Pos2(height, width);
+    let mut animator = Animator::new();
     let baseline_chaos = (0..100)
         .map(|s| {
-            let robots = robots.iter().map(|r| r.go(s, width, height));
-            calc_chaos(robots, width, height)
+            show(&mut animator, size, &robots, s, "");
+            calc_chaos(size, &robots, s)
         })
         .min()
         .unwrap();
     // Taking 80% of the lowest value of the first 100 secs.
     let baseline_chaos = baseline_chaos * 4 / 5;
-    let result = (101..width * height)
+    let result = (101..(width * height) as isize)
         .into_par_iter()
         .by_exponential_blocks()
-        .find_map_first(|s| {
-            let robots = robots.iter().map(|r| r.go(s, width, height));
-            (calc_chaos(robots, width, height) < baseline_chaos).then_some(s)
-        })
-        .expect("christmas tree not found");
-    dump_grid(robots.into_iter().map(|r| r.go(result, width, height)));
-    result
+        .find_map_first(|s| (calc_chaos(size, &robots, s) < baseline_chaos).then_some(s))
+        .ok_or_else(|| eyre!("christmas tree not found"))?;
+    show(&mut animator, size, &robots, result, ", christmas tree!");
+    Ok(result)
 }
 
-fn dump_grid(robots: impl IntoIterator<Item = Robot>) {
-    let grid =
-        pathfinding::Grid::from_coordinates(&robots.into_iter().map(|r| r.pos).collect_vec())
-            .unwrap();
-    eprintln!("{grid:?}");
+/// Shows the number of robots on every tile of an area of `size` after `secs`,
+/// like the puzzle does (with `--animate`).
+fn show(animator: &mut Animator, size: Pos2, robots: &[Robot], secs: isize, note: &str) {
+    if !animator.is_enabled() {
+        return;
+    }
+    let mut tiles = Grid::new(size.0, size.1, 0);
+    let mut wrapping = tiles.wrapping_mut();
+    for robot in robots {
+        wrapping[robot.go(secs)] += 1;
+    }
+    let frame = tiles.render(|n| match n {
+        0 => '.',
+        n => char::from_digit(n.min(9) as u32, 10).unwrap(),
+    });
+    animator.frame_with_status(frame, format!("{secs} secs{note}"));
 }
 
-fn parse(input: SS) -> impl Iterator<Item = Robot> {
-    input.lines().map(|line| {
-        let (p, v) = line.strip_prefix("p=").unwrap().split_once(" v=").unwrap();
-        Robot {
-            pos: p.split(',').map(to_isize).collect_tuple().unwrap(),
-            dir: v.split(',').map(to_isize).collect_tuple().unwrap(),
-        }
+fn parse(input: SS) -> Result<Vec<Robot>> {
+    parse_lines(input, |line| {
+        let (px, py, vx, vy) = scan("p={},{} v={},{}", line)?;
+        Ok(Robot {
+            pos: Pos2(py, px),
+            dir: Dir2(vy, vx),
+        })
     })
+    .try_collect()
 }
 
-fn calc_chaos(robots: impl IntoIterator<Item = Robot>, width: isize, height: isize) -> usize {
+/// The product of the number of robots in every quadrant of an area of `size`
+/// after `secs`. This runs thousands of times, so the robots are not counted
+/// in a grid of tiles like [`show`] does.
+fn calc_chaos(size: Pos2, robots: &[Robot], secs: isize) -> usize {
     let mut qs = [0; 4];
-    let half_w = width / 2;
-    let half_h = height / 2;
-    for r in robots {
-        let (x, y) = r.pos;
+    let (half_h, half_w) = (size.0 / 2, size.1 / 2);
+    for robot in robots {
+        let Pos2(y, x) = robot.go(secs).rem_euclid(size);
         if x != half_w && y != half_h {
             let idx = if x < half_w { 0 } else { 1 } + if y < half_h { 0 } else { 2 };
             qs[idx] += 1
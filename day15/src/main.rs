@@ -1,107 +1,138 @@
 use common::*;
-use std::{mem, thread::sleep, time::Duration};
+use std::mem;
 
-fn part1(input: SS) -> usize {
-    let (mut grid, moves) = parse(input, |c| [c]);
-    let mut robot = grid.positions('@').exactly_one().ok().unwrap();
-    grid[robot] = '.';
+grid_cell! {
+    enum Warehouse {
+        Wall = '#',
+        Floor = '.',
+        Robot = '@',
+        Crate = 'O',
+        // moves like a crate, but does not count for the GPS score
+        Decoy = 'o',
+        CrateLeft = '[',
+        CrateRight = ']',
+    }
+}
+
+use Warehouse::*;
 
-    #[cfg(not(test))]
-    dump_grid(&mut grid, robot);
+fn part1(input: SS) -> Result<usize> {
+    let (mut grid, moves) = parse(input, false)?;
+    let mut robot = find_robot(&mut grid)?;
+    let mut animator = Animator::new();
+    show(&mut animator, &grid, robot);
 
     for m in moves {
         let (next_pos, found_there) = grid.step(robot, m).unwrap();
-        if found_there == '#' {
+        match found_there {
             // impossible move
-            continue;
-        }
-        if found_there == 'O' || found_there == 'o' {
-            // see if we can move some blocks;
-            let Some((move_to, _)) = grid
-                .walk(robot, m)
-                .skip(1)
-                .take_while(|(_, c)| *c != '#')
-                .find(|(_, c)| *c == '.')
-            else {
-                continue;
-            };
-            grid.swap(next_pos, move_to);
+            Wall => continue,
+            Floor => (),
+            Crate | Decoy => {
+                // see if we can move some blocks;
+                let Some((move_to, _)) = grid
+                    .walk(robot, m)
+                    .skip(1)
+                    .take_while(|(_, c)| *c != Wall)
+                    .find(|(_, c)| *c == Floor)
+                else {
+                    continue;
+                };
+                grid.swap(next_pos, move_to);
+            }
+            Robot | CrateLeft | CrateRight => bail!("unexpected {found_there:?} at {next_pos:?}"),
         }
         robot = next_pos;
-
-        #[cfg(not(test))]
-        dump_grid(&mut grid, robot);
+        show(&mut animator, &grid, robot);
     }
 
-    grid.positions('O').map(|p| p.0 * 100 + p.1).sum()
+    Ok(grid.positions(Crate).map(|p| p.0 * 100 + p.1).sum())
 }
 
-fn part2(input: SS) -> usize {
-    let (mut grid, moves) = parse(input, |c| match c {
-        c @ ('#' | '.') => [c; 2],
-        '@' => ['@', '.'],
-        'O' => ['[', ']'],
-        _ => panic!("unexpected character in grid: {c}"),
-    });
-    let mut robot = grid.positions('@').exactly_one().ok().unwrap();
-    grid[robot] = '.';
+fn part2(input: SS) -> Result<usize> {
+    let (mut grid, moves) = parse(input, true)?;
+    let mut robot = find_robot(&mut grid)?;
     let mut sandbox = grid.clone();
+    let mut animator = Animator::new();
+    show(&mut animator, &grid, robot);
     for dir in moves {
         let (next_pos, found_there) = grid.step(robot, dir).unwrap();
         match found_there {
             // impossible move
-            '#' => (),
+            Wall => (),
             // easy move
-            '.' => robot = next_pos,
+            Floor => robot = next_pos,
             // Ok, we need to move some boxes... Let's try so in our sandbox.
-            '[' | ']' => {
+            CrateLeft | CrateRight => {
                 sandbox.clone_from(&grid);
                 if move_box(&mut sandbox, next_pos, found_there, dir).is_ok() {
                     robot = next_pos;
                     mem::swap(&mut grid, &mut sandbox);
                 }
             }
-            _ => unreachable!(),
+            Robot | Crate | Decoy => bail!("unexpected {found_there:?} at {next_pos:?}"),
         }
+        show(&mut animator, &grid, robot);
     }
 
-    #[cfg(not(test))]
-    dump_grid(&mut grid, robot);
+    Ok(grid.positions(CrateLeft).map(|p| p.0 * 100 + p.1).sum())
+}
 
-    grid.positions('[').map(|p| p.0 * 100 + p.1).sum()
+/// Parses the warehouse (twice as wide for part 2) and the moves of the robot.
+fn parse(input: SS, wide: bool) -> Result<(Grid<Warehouse>, Vec<Dir2>)> {
+    let [grid, moves] = split_sections(input)?;
+    let grid: Grid<Warehouse> = grid.parse()?;
+    let grid = if wide {
+        ensure!(
+            grid.positions(Decoy).next().is_none(),
+            "decoys in a wide warehouse"
+        );
+        grid.scale_columns(|cell| match cell {
+            Crate => [CrateLeft, CrateRight],
+            Robot => [Robot, Floor],
+            cell => [cell, cell],
+        })
+    } else {
+        grid
+    };
+    let moves = moves
+        .chars()
+        .filter(|&c| c != '\n')
+        .map(|c| match c {
+            '>' => Ok(E),
+            '^' => Ok(N),
+            '<' => Ok(W),
+            'v' => Ok(S),
+            _ => bail!("unexpected character in moves: {c:?}"),
+        })
+        .try_collect()?;
+    Ok((grid, moves))
 }
 
-fn parse<F, I>(input: SS, grid_mapper: F) -> (Grid, impl Iterator<Item = Dir2>)
-where
-    F: Fn(char) -> I,
-    I: IntoIterator<Item = char>,
-{
-    let (grid, moves) = input.split_once("\n\n").unwrap();
-    let grid: Grid = grid
-        .lines()
-        .map(|line| line.chars().flat_map(&grid_mapper))
-        .collect();
-    let moves = moves.chars().filter_map(|c| match c {
-        '>' => Some(E),
-        '^' => Some(N),
-        '<' => Some(W),
-        'v' => Some(S),
-        '\n' => None,
-        _ => panic!("unexpected character: {c:?}"),
-    });
-    (grid, moves)
+/// Finds the robot and replaces it with floor, its position is tracked
+/// separately.
+fn find_robot(grid: &mut Grid<Warehouse>) -> Result<Pos2> {
+    let robot = grid
+        .positions(Robot)
+        .exactly_one()
+        .map_err(|_| eyre!("expected exactly one robot in the grid"))?;
+    grid[robot] = Floor;
+    Ok(robot)
 }
 
-#[allow(dead_code, reason = "used to animate the story of the robot on run")]
-fn dump_grid(grid: &mut Grid, robot: Pos2) {
-    grid[robot] = '@';
-    println!("{CLEAR_TERM}{grid:?}");
-    sleep(Duration::from_millis(100));
-    grid[robot] = '.';
+/// Animates the story of the robot (with `--animate`).
+fn show(animator: &mut Animator, grid: &Grid<Warehouse>, robot: Pos2) {
+    if !animator.is_enabled() {
+        return;
+    }
+    animator.frame(
+        grid.render(Warehouse::to_char)
+            .overlay([robot], Highlight::Both('@', Color::Yellow)),
+    );
 }
 
-fn move_box(grid: &mut Grid, pos: Pos2, ch: char, dir: Dir2) -> Result<(), ()> {
-    let pos = if ch == '[' {
+fn move_box(grid: &mut Grid<Warehouse>, pos: Pos2, cell: Warehouse, dir: Dir2) -> Result<(), ()> {
+    let pos = if cell == CrateLeft {
         pos
     } else {
         pos.saturating_add_dir(W)
@@ -124,28 +155,30 @@ fn move_box(grid: &mut Grid, pos: Pos2, ch: char, dir: Dir2) -> Result<(), ()> {
                 clear_pos = pos.saturating_add_dir(E);
             }
             match grid[check_pos] {
-                c @ ('[' | ']') => move_box(grid, check_pos, c, dir)?,
-                '#' => return Err(()),
-                c => assert_eq!(c, '.'),
+                c @ (CrateLeft | CrateRight) => move_box(grid, check_pos, c, dir)?,
+                Wall => return Err(()),
+                Floor => (),
+                c @ (Robot | Crate | Decoy) => unreachable!("{c:?} in a wide warehouse"),
             }
-            grid[new_left_pos] = '[';
-            grid[new_right_pos] = ']';
-            grid[clear_pos] = '.';
+            grid[new_left_pos] = CrateLeft;
+            grid[new_right_pos] = CrateRight;
+            grid[clear_pos] = Floor;
         }
         N | S => {
             let new_pos = pos.saturating_add_dir(dir);
             let other_pos = new_pos.saturating_add_dir(E);
             for p in [new_pos, other_pos] {
                 match grid[p] {
-                    c @ ('[' | ']') => move_box(grid, p, c, dir)?,
-                    '#' => return Err(()),
-                    c => assert_eq!(c, '.'),
+                    c @ (CrateLeft | CrateRight) => move_box(grid, p, c, dir)?,
+                    Wall => return Err(()),
+                    Floor => (),
+                    c @ (Robot | Crate | Decoy) => unreachable!("{c:?} in a wide warehouse"),
                 }
             }
-            grid[new_pos] = '[';
-            grid[other_pos] = ']';
-            grid[pos] = '.';
-            grid[pos.saturating_add_dir(E)] = '.';
+            grid[new_pos] = CrateLeft;
+            grid[other_pos] = CrateRight;
+            grid[pos] = Floor;
+            grid[pos.saturating_add_dir(E)] = Floor;
         }
         _ => unreachable!(),
     }
@@ -153,7 +186,7 @@ fn move_box(grid: &mut Grid, pos: Pos2, ch: char, dir: Dir2) -> Result<(), ()> {
 }
 
 boilerplate! {
-    part1 => { kid -> 0 }
+    part1 => { kid -> 0, decoys -> 1617 }
     part1 => { test1 -> 10092, test2 -> 2028, real -> 1413675 }
     part2 => { test1 -> 9021, real -> 1399772 }
 }
@@ -3,46 +3,33 @@ use common::*;
 const ROTATE_COST: usize = 1000;
 const STEP_COST: usize = 1;
 
-fn part1(input: SS) -> usize {
-    let (grid, start, end) = parse(input);
-    let (_, cost) = pathfinding::astar(
-        &(start, E),
-        |(pos, dir)| successors(&grid, *pos, *dir),
-        |(pos, _)| pos.abs_diff(end),
-        |(p, _)| *p == end,
-    )
-    .unwrap();
-    cost
+fn part1(input: SS) -> Result<usize> {
+    let (grid, start, end) = parse(input)?;
+    let (_, cost) = grid
+        .search(|_, (_, to)| (to != '#').then_some(STEP_COST))
+        // the reindeer starts facing east and every rotation costs extra
+        .turns(Heading::E, ROTATE_COST)
+        .heuristic(|pos| pos.abs_diff(end))
+        .best(start, |pos| pos == end)
+        .ok_or_else(|| eyre!("no path from start to end"))?;
+    Ok(cost)
 }
 
-fn part2(input: SS) -> usize {
-    let (grid, start, end) = parse(input);
-    let (paths, _) = pathfinding::astar_bag(
-        &(start, E),
-        |&(pos, dir)| successors(&grid, pos, dir),
-        |(pos, _)| pos.abs_diff(end),
-        |(p, _)| *p == end,
-    )
-    .unwrap();
-    paths.flatten().map(first).unique().count()
+fn part2(input: SS) -> Result<usize> {
+    let (grid, start, end) = parse(input)?;
+    let (paths, _) = grid
+        .search(|_, (_, to)| (to != '#').then_some(STEP_COST))
+        // the reindeer starts facing east and every rotation costs extra
+        .turns(Heading::E, ROTATE_COST)
+        .heuristic(|pos| pos.abs_diff(end))
+        .all_best(start, |pos| pos == end)
+        .ok_or_else(|| eyre!("no path from start to end"))?;
+    Ok(paths.flatten().unique().count())
 }
 
-fn parse(input: &str) -> (Grid, Pos2, Pos2) {
-    let grid = Grid::from(input);
-    let start = grid.positions('S').exactly_one().ok().unwrap();
-    let end = grid.positions('E').exactly_one().ok().unwrap();
-    (grid, start, end)
-}
-
-fn successors(grid: &Grid, pos: Pos2, dir: Dir2) -> impl Iterator<Item = ((Pos2, Dir2), usize)> {
-    let (step, found) = grid.step(pos, dir).unwrap();
-    (found != '#')
-        .then_some(((step, dir), STEP_COST))
-        .into_iter()
-        .chain([
-            ((pos, dir.rotate_90_cw()), ROTATE_COST),
-            ((pos, dir.rotate_90_ccw()), ROTATE_COST),
-        ])
+fn parse(input: &str) -> Result<(Grid, Pos2, Pos2)> {
+    let (grid, markers) = Grid::parse_with_markers(input, &['S', 'E'], |ch| ch)?;
+    Ok((grid, markers[&'S'], markers[&'E']))
 }
 
 boilerplate! {
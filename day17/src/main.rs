@@ -71,31 +71,19 @@ impl Cpu {
         })
     }
 
-    fn parse(mut input: SS) -> Self {
-        let mut read = |prefix| {
-            let (a, b) = input
-                .strip_prefix(prefix)
-                .unwrap()
-                .split_once('\n')
-                .unwrap();
-            input = b;
-            a
-        };
-
-        Self {
-            a: to_usize(read("Register A: ")),
-            b: to_usize(read("Register B: ")),
-            c: to_usize(read("Register C: ")),
+    fn parse(input: SS) -> Result<Self> {
+        const TEMPLATE: &str = "Register A: {}\nRegister B: {}\nRegister C: {}\n\nProgram: {}";
+
+        let (a, b, c, program): (_, _, _, String) = scan(TEMPLATE, input.trim_end())?;
+        Ok(Self {
+            a,
+            b,
+            c,
             pc: 0,
-            program: input
-                .trim()
-                .strip_prefix("Program: ")
-                .unwrap()
-                .split(',')
-                .map(to_usize)
-                .collect_vec()
+            program: comma_separated(&program)
+                .wrap_err("invalid program on line 5")?
                 .leak(),
-        }
+        })
     }
 
     fn with_a(&self, a: usize) -> Self {
@@ -103,16 +91,16 @@ impl Cpu {
     }
 }
 
-fn part1(input: SS) -> String {
-    Cpu::parse(input).exec_program().join(",")
+fn part1(input: SS) -> Result<String> {
+    Ok(Cpu::parse(input)?.exec_program().join(","))
 }
 
-fn part2a(input: SS) -> usize {
-    let cpu = Cpu::parse(input);
+fn part2a(input: SS) -> Result<usize> {
+    let cpu = Cpu::parse(input)?;
     let program = cpu.program;
     (0..usize::MAX)
         .find(|&a| cpu.with_a(a).exec_program().eq(program.iter().copied()))
-        .unwrap()
+        .ok_or_else(|| eyre!("no value for register A reproduces the program"))
 }
 
 /*
@@ -191,8 +179,8 @@ AHA! So, now we have two possibilities. This starts to look like a tree search
 and we know how to do that!
 */
 
-fn part2b(input: SS) -> usize {
-    let cpu = Cpu::parse(input);
+fn part2b(input: SS) -> Result<usize> {
+    let cpu = Cpu::parse(input)?;
     let program = cpu.program;
     let mut possibilities = vec![0b100_usize];
     for start in (0..program.len() - 1).rev() {
@@ -204,7 +192,10 @@ fn part2b(input: SS) -> usize {
             .filter(|a| cpu.with_a(*a).exec_program().eq(tail.iter().copied()))
             .collect();
     }
-    possibilities.into_iter().min().unwrap()
+    possibilities
+        .into_iter()
+        .min()
+        .ok_or_else(|| eyre!("no value for register A reproduces the program"))
 }
 
 boilerplate! {
@@ -1,52 +1,43 @@
 use common::*;
 
-fn part1(input: SS, size: usize, fallen_bytes: usize) -> usize {
-    let mut grid = pathfinding::Grid::new(size, size);
-    grid.fill();
+fn part1(input: SS, size: usize, fallen_bytes: usize) -> Result<usize> {
+    let mut grid = Grid::new(size, size, true);
     for pos in parse(input).take(fallen_bytes) {
-        grid.remove_vertex(pos);
+        fall(&mut grid, pos?)?;
     }
-    let target = (size - 1, size - 1);
-    get_route(&grid, target).unwrap().1
+    let target = Pos2(size - 1, size - 1);
+    grid.distance_map([Pos2(0, 0)], Neighbourhood::Manhattan, |_, open| open)[target]
+        .ok_or_else(|| eyre!("no route to the exit"))
 }
 
-fn part2(input: SS, size: usize, known_ok_value: usize) -> String {
-    let mut grid = pathfinding::Grid::new(size, size);
-    grid.fill();
-    let mut iter = parse(input);
-    for pos in iter.by_ref().take(known_ok_value) {
-        grid.remove_vertex(pos);
+fn part2(input: SS, size: usize, known_ok_value: usize) -> Result<String> {
+    let mut grid = Grid::new(size, size, true);
+    let mut bytes = parse(input);
+    for pos in bytes.by_ref().take(known_ok_value) {
+        fall(&mut grid, pos?)?;
     }
-    let target = (size - 1, size - 1);
-    let mut optimal_route = get_route(&grid, target).unwrap().0;
-    for pos in iter {
-        grid.remove_vertex(pos);
-        if optimal_route.contains(&pos) {
-            let Some((route, _)) = get_route(&grid, target) else {
-                return format!("{},{}", pos.0, pos.1);
-            };
-            optimal_route = route;
-        }
-    }
-    panic!("no obstruction found")
+    let bytes: Vec<_> = bytes.try_collect()?;
+    let target = Pos2(size - 1, size - 1);
+    let (_, Pos2(y, x)) = grid
+        .first_cut(bytes, Pos2(0, 0), target, Neighbourhood::Manhattan)
+        .ok_or_else(|| eyre!("no obstruction found"))?;
+    Ok(format!("{x},{y}"))
 }
 
-fn get_route(
-    grid: &pathfinding::Grid,
-    target: (usize, usize),
-) -> Option<(Vec<(usize, usize)>, usize)> {
-    pathfinding::astar(
-        &(0, 0),
-        |p| grid.neighbours(*p).into_iter().map(|p| (p, 1)),
-        |p| grid.distance(*p, target),
-        |p| *p == target,
-    )
+fn fall(grid: &mut Grid<bool>, pos: Pos2) -> Result<()> {
+    let Some(cell) = grid.get_mut(pos) else {
+        bail!("byte at {},{} is outside the memory space", pos.1, pos.0);
+    };
+    *cell = false;
+    Ok(())
 }
 
-fn parse(input: SS) -> impl Iterator<Item = (usize, usize)> {
-    input
-        .lines()
-        .map(|line| line.split(',').map(to_usize).collect_tuple().unwrap())
+/// The positions of the falling bytes, the input has `x,y` coordinates.
+fn parse(input: SS) -> impl Iterator<Item = Result<Pos2>> {
+    parse_lines(input, |line| {
+        let (x, y) = scan("{},{}", line)?;
+        Ok(Pos2(y, x))
+    })
 }
 
 boilerplate! {
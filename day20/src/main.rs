@@ -1,7 +1,7 @@
 use common::*;
 use std::collections::HashSet;
 
-fn go(input: SS, cheat_length: usize, target: usize) -> usize {
+fn go(input: SS, cheat_length: usize, target: usize) -> Result<usize> {
     let mut cheat_space = HashSet::from(DIRECTIONS_4);
     for _ in 1..cheat_length {
         cheat_space.extend(
@@ -12,38 +12,34 @@ fn go(input: SS, cheat_length: usize, target: usize) -> usize {
         );
     }
     let cheat_space = &cheat_space.into_iter().collect_vec();
-    let (start, end, ref grid) = parse(input);
-    let mut track = Grid::new(grid.rows(), grid.columns(), 0);
-    let path = grid
-        .astar_flat(start, end, Neighbourhood::Manhattan)
-        .unwrap()
-        .0
-        .enumerate()
-        .collect_vec();
-    for &(cost, pos) in path.iter() {
-        track[pos] = cost;
-    }
+    let (start, end, ref grid) = parse(input)?;
+    let from_start = grid.distance_map([start], Neighbourhood::Manhattan, |_, open| open);
+    let to_end = grid.distance_map([end], Neighbourhood::Manhattan, |_, open| open);
+    let best = from_start[end].ok_or_else(|| eyre!("no path from start to end"))?;
 
-    path.into_par_iter()
-        .map(|(cost_start, cheat_start)| {
+    Ok(from_start
+        .items()
+        .filter_map(|(pos, cost)| Some((pos, cost?)))
+        .collect_vec()
+        .into_par_iter()
+        .map(|(cheat_start, cost_start)| {
             cheat_space
                 .iter()
-                .flat_map(|&d| {
+                .filter(|&&d| {
                     let cheat_length = d.0.unsigned_abs() + d.1.unsigned_abs();
-                    let (cheat_end, cost_end) = track.step(cheat_start, d)?;
-                    (cost_end >= cost_start + cheat_length + target).then_some(cheat_end)
+                    let Some((_, Some(cost_end))) = to_end.step(cheat_start, d) else {
+                        return false;
+                    };
+                    cost_start + cheat_length + cost_end + target <= best
                 })
-                .unique()
                 .count()
         })
-        .sum()
+        .sum())
 }
 
-fn parse(input: SS) -> (Pos2, Pos2, Grid<bool>) {
-    let grid = Grid::from(input);
-    let start = grid.positions('S').exactly_one().ok().unwrap();
-    let end = grid.positions('E').exactly_one().ok().unwrap();
-    (start, end, grid.map(|ch| *ch != '#'))
+fn parse(input: SS) -> Result<(Pos2, Pos2, Grid<bool>)> {
+    let (grid, markers) = Grid::parse_with_markers(input, &['S', 'E'], |ch| ch != '#')?;
+    Ok((markers[&'S'], markers[&'E'], grid))
 }
 
 boilerplate! {
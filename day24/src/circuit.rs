@@ -1,4 +1,7 @@
-use common::{Itertools, SS, second, to_usize};
+use common::{
+    Itertools, Result, SS, bail, key_values, parse_lines, second, split_sections, to_usize,
+    try_usize,
+};
 use id_arena::{Arena, Id};
 use std::collections::{BTreeSet, HashMap};
 
@@ -132,34 +135,38 @@ impl Circuit {
         value
     }
 
-    pub fn parse(input: SS) -> Self {
+    pub fn parse(input: SS) -> Result<Self> {
         let mut circuit = Self::default();
-        let mut lines = input.lines();
-        for line in lines.by_ref() {
-            if line.is_empty() {
-                break;
-            }
-            let (name, value) = line.split_once(": ").unwrap();
-            circuit.wire_mut(name).value = Some(to_usize(value));
+        let [wires, gates] = split_sections(input)?;
+        for wire in key_values(wires) {
+            let (name, value) = wire?;
+            circuit.wire_mut(name).value = Some(try_usize(value)?);
         }
 
-        for line in lines {
-            let (lhs, op, rhs, arrow, out) = line.split_whitespace().collect_tuple().unwrap();
-            assert_eq!(arrow, "->");
+        let gates = parse_lines(gates, |line| {
+            let Some((lhs, op, rhs, "->", out)) = line.split_whitespace().collect_tuple() else {
+                bail!("expected `<wire> <op> <wire> -> <wire>`");
+            };
             let op = match op {
                 "AND" => Op::And,
                 "OR" => Op::Or,
                 "XOR" => Op::Xor,
-                _ => panic!("unknown gate: {op}"),
+                _ => bail!("unknown gate: {op}"),
             };
             let mut inp = [lhs, rhs];
             inp.sort();
-            let id = circuit.gates.alloc(Gate { inp, op, out });
-            circuit.wire_mut(inp[0]).outputs.insert(id);
-            circuit.wire_mut(inp[1]).outputs.insert(id);
+            Ok(Gate { inp, op, out })
+        });
+        for gate in gates {
+            let gate = gate?;
+            let [lhs, rhs] = gate.inp;
+            let out = gate.out;
+            let id = circuit.gates.alloc(gate);
+            circuit.wire_mut(lhs).outputs.insert(id);
+            circuit.wire_mut(rhs).outputs.insert(id);
             circuit.wire_mut(out).input = Some(id);
         }
-        circuit
+        Ok(circuit)
     }
 
     pub fn swap_outs(&mut self, a: SS, b: SS) {
@@ -5,19 +5,19 @@ use std::collections::BTreeMap;
 
 mod circuit;
 
-fn part1(input: SS) -> usize {
-    let mut circuit = Circuit::parse(input);
-    circuit
+fn part1(input: SS) -> Result<usize> {
+    let mut circuit = Circuit::parse(input)?;
+    Ok(circuit
         .wires()
         .filter_map(|w| Some((w.name, w.as_z()?)))
         .collect_vec()
         .into_iter()
         .map(|(name, nr)| circuit.eval(name) << nr)
-        .sum()
+        .sum())
 }
 
-fn part2(input: SS) -> String {
-    let circuit = Circuit::parse(input);
+fn part2(input: SS) -> Result<String> {
+    let circuit = Circuit::parse(input)?;
 
     // Try to find a circuit that contains a correct Ripple-carry adder which is
     // what we are looking for here.
@@ -39,13 +39,13 @@ fn part2(input: SS) -> String {
         },
         |swaps| check_correct_adders(&circuit, swaps).is_ok(),
     )
-    .unwrap()
+    .ok_or_else(|| eyre!("no swaps found that result in a ripple-carry adder"))?
     .into_iter()
     .last()
     .unwrap();
 
     result.sort();
-    result.join(",")
+    Ok(result.join(","))
 }
 
 /// Returns `Ok` if this is THE circuit that we are looking for, otherwise `Err`
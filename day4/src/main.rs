@@ -14,23 +14,10 @@ fn part1(input: SS) -> usize {
         .count()
 }
 
-fn part2(input: SS) -> usize {
+fn part2(input: SS) -> Result<usize> {
     let grid = Grid::from(input);
-    grid.positions('A')
-        .filter(|&from| {
-            matches!((
-                grid.get(from + NE),
-                grid.get(from + NW),
-                grid.get(from + SW),
-                grid.get(from + SE)
-            ), (
-                Some(ne @ ('M'|'S')),
-                Some(nw @ ('M'|'S')),
-                Some(sw @ ('M'|'S')),
-                Some(se @ ('M'|'S'))
-            ) if ne != sw && nw != se)
-        })
-        .count()
+    let x_mas = Pattern::parse("M.S\n.A.\nM.S", '.')?;
+    Ok(grid.find_pattern_oriented(&x_mas).count())
 }
 
 boilerplate! {
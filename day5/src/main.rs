@@ -5,9 +5,10 @@ type Page = u8;
 type Rule = [Page; 2];
 type Rules = HashSet<Rule>;
 
-fn part1(input: SS) -> usize {
-    let (rules, updates) = parse(input);
-    updates
+fn part1(input: SS) -> Result<usize> {
+    let (rules, updates) = parse(input)?;
+    Ok(updates
+        .into_iter()
         .filter(|pages| {
             pages.is_sorted_by(|&a, &b| {
                 let result = rules.contains(&[a, b]);
@@ -19,12 +20,13 @@ fn part1(input: SS) -> usize {
             })
         })
         .map(|pages| pages[pages.len() / 2] as usize)
-        .sum()
+        .sum())
 }
 
-fn part2(input: SS) -> usize {
-    let (rules, updates) = parse(input);
-    updates
+fn part2(input: SS) -> Result<usize> {
+    let (rules, updates) = parse(input)?;
+    Ok(updates
+        .into_iter()
         .filter_map(|mut pages| {
             if pages.is_sorted_by(|&a, &b| rules.contains(&[a, b])) {
                 return None;
@@ -39,22 +41,20 @@ fn part2(input: SS) -> usize {
             Some(pages)
         })
         .map(|pages| pages[pages.len() / 2] as usize)
-        .sum()
+        .sum())
 }
 
-fn parse(input: SS) -> (Rules, impl Iterator<Item = Vec<Page>>) {
-    let (rule_lines, update_lines) = input.split_once("\n\n").unwrap();
-    let rules = rule_lines
-        .lines()
-        .map(|line| {
-            let (left, right) = line.split_once('|').unwrap();
-            [left.parse().unwrap(), right.parse().unwrap()]
-        })
-        .collect();
-    let updates = update_lines
-        .lines()
-        .map(|line| line.split(',').map(|nr| nr.parse().unwrap()).collect());
-    (rules, updates)
+fn parse(input: SS) -> Result<(Rules, Vec<Vec<Page>>)> {
+    let [rule_lines, update_lines] = split_sections(input)?;
+    let rules = parse_lines(rule_lines, |line| {
+        let (left, right) = line
+            .split_once('|')
+            .ok_or_else(|| eyre!("expected `<page>|<page>`"))?;
+        Ok([try_parse(left)?, try_parse(right)?])
+    })
+    .try_collect()?;
+    let updates = parse_lines(update_lines, comma_separated).try_collect()?;
+    Ok((rules, updates))
 }
 
 boilerplate! {
@@ -1,60 +1,85 @@
 use common::*;
 use std::iter;
 
-fn part1(input: SS) -> usize {
-    let (grid, pos, dir) = parse_grid(input);
+fn part1(input: SS) -> Result<usize> {
+    let (grid, pos, dir) = parse_grid(input)?;
+    let mut animator = Animator::new();
+    let mut path = vec![pos];
     // count all unique positions that are encountered during a walk
-    walk_grid(&grid, pos, dir)
-        .map(|(pos, _)| pos)
+    Ok(walk_grid(&grid, pos, dir)
+        .map(|(pos, heading)| {
+            if animator.is_enabled() {
+                path.push(pos);
+                let frame = grid
+                    .render(|c| c)
+                    .overlay(path.iter().copied(), Color::Cyan)
+                    .overlay([pos], Highlight::Both(heading.to_char(), Color::Yellow));
+                animator.frame_with_status(frame, format!("{} steps", path.len() - 1));
+            }
+            pos
+        })
         // including the start position (just to be sure, not needed for my input)
         .chain(iter::once(pos))
         .unique()
-        .count()
+        .count())
 }
 
-fn part2(input: SS) -> usize {
-    let (grid, pos, dir) = parse_grid(input);
+fn part2(input: SS) -> Result<usize> {
+    let (grid, pos, dir) = parse_grid(input)?;
     // first collect all unique positions to place an extra obstruction,
     // including the position and direction we came from the first time we
     // encountered this position on the grid
-    walk_grid(&grid, pos, dir)
+    Ok(walk_grid(&grid, pos, dir)
         .tuple_windows()
         .unique_by(|((_, _), (extra, _))| *extra)
         .collect_vec()
         // then process this list in parallel
         .into_par_iter()
-        .map_with(grid, |grid, ((pos, dir), (extra, _))| {
+        .map_with(grid, |grid, ((pos, heading), (extra, _))| {
             grid[extra] = '#';
-            let loops = !walk_grid(grid, pos, dir).all_unique();
+            let loops = loops(grid, pos, heading);
             grid[extra] = '.';
             if loops { 1 } else { 0 }
         })
-        .sum()
+        .sum())
 }
 
-fn parse_grid(input: SS) -> (Grid, Pos2, Dir2) {
-    let grid = Grid::from(input);
-    let pos = grid.positions('^').next().expect("actor not found");
-    (grid, pos, N)
+fn parse_grid(input: SS) -> Result<(Grid, Pos2, Heading)> {
+    let (grid, markers) = Grid::parse_with_markers(input, &['^'], |ch| match ch {
+        '^' => '.',
+        ch => ch,
+    })?;
+    Ok((grid, markers[&'^'], Heading::N))
 }
 
 fn walk_grid(
     grid: &Grid,
     mut pos: Pos2,
-    mut dir: Dir2,
-) -> impl Iterator<Item = (Pos2, Dir2)> + use<'_> {
+    mut heading: Heading,
+) -> impl Iterator<Item = (Pos2, Heading)> + use<'_> {
     iter::from_fn(move || {
         loop {
-            let (next, ch) = grid.step(pos, dir)?;
+            let (next, ch) = grid.step(pos, heading.dir())?;
             if ch != '#' {
                 pos = next;
-                return Some((pos, dir));
+                return Some((pos, heading));
             }
-            dir = dir.rotate_90_cw();
+            heading = heading.turn_right();
         }
     })
 }
 
+/// The guard is in a loop once it is back on a position with the same heading,
+/// the headings seen on every position are kept as a bitset.
+fn loops(grid: &Grid, pos: Pos2, heading: Heading) -> bool {
+    let mut seen = Grid::new(grid.rows(), grid.columns(), 0u8);
+    walk_grid(grid, pos, heading).any(|(pos, heading)| {
+        let again = seen[pos] & heading.bit() != 0;
+        seen[pos] |= heading.bit();
+        again
+    })
+}
+
 boilerplate! {
     part1 => { test -> 41, real -> 5067 }
     part2 => { test -> 6, real -> 1793 }
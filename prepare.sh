@@ -11,6 +11,9 @@ set -euo pipefail
 echo Preparing ${1:?"Please provide the name of the day to add, example: ./prepare.sh day11"}
 cargo new $1
 cargo add common -p $1
+printf '\n[lints]\nworkspace = true\n' >>$1/Cargo.toml
+# let the `aoc` runner discover the new day
+touch aoc/build.rs
 
 cat >$1/src/main.rs <<EOF
 use common::*;